@@ -4,16 +4,27 @@
 // Standard
 use std::env;
 use std::error::Error;
-use std::path::PathBuf;
 use std::fs;
+use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::thread;
 
 // 3rd Party
 use home::home_dir;
 use once_cell::sync::Lazy;
+use serde_json::{json, Value};
 use structopt::StructOpt;
 
 // Project
-use rust_multi_json_benchmark::json_generator::Generator;
+use rust_multi_json_benchmark::{
+    json_generator::{self, FanOutDistribution, GeneratedJson, Generator, GeneratorOptions, Manifest, Needle, NeedlePosition, NodeCounts, TreeShape},
+    presets::Preset,
+    schema_generator::SchemaGenerator,
+    shape_statistics::ShapeStatistics,
+    sweep::SweepSpec,
+    utils::{json_type::{LeafOptions, LeafTypeWeights, NumberProfile, StringProfile}, randomizer, unicode_escaping_writer::UnicodeEscapingWriter, checksum::Checksum, output_format::{self, OutputFormat}},
+};
 /* #endregion */
 
 /* #region Default Values */
@@ -34,41 +45,453 @@ static DEFAULT_PATH_TO_SAVE_FILE: Lazy<String> = Lazy::new(|| {
 });
 /* #endregion */
 
+/* #region CLI Arguments */
+fn parse_size(source: &str) -> Result<u64, Box<dyn Error>> {
+    let source = source.trim().to_uppercase();
+    let unit_index = source.find(|character: char| !character.is_ascii_digit()).unwrap_or(source.len());
+    let (number, unit) = source.split_at(unit_index);
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        other => return Err(format!("Unknown size unit: {}", other).into()),
+    };
+    let size = number.parse::<u64>()?
+        .checked_mul(multiplier)
+        .ok_or("The size is too big")?;
+    if size == 0 {
+        Err("The size has to be none zero".into())
+    } else {
+        Ok(size)
+    }
+}
+
+fn parse_none_zero_u64(source: &str) -> Result<u64, Box<dyn Error>> {
+    match source.parse::<u64>()? {
+        0 => Err("The number has to be none zero".into()),
+        number => Ok(number),
+    }
+}
+
+fn parse_leaf_type_weights(source: &str) -> Result<LeafTypeWeights, Box<dyn Error>> {
+    let weights = source
+        .split(',')
+        .map(|weight| weight.trim().parse::<u32>())
+        .collect::<Result<Vec<u32>, _>>()?;
+    match weights[..] {
+        [null, bool, number, string] => Ok(LeafTypeWeights { null, bool, number, string }),
+        _ => Err("Expected 4 weights: NULL,BOOL,NUMBER,STRING".into()),
+    }
+}
+
 /// Generates JSON file for testing
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
 #[structopt(name = "jsonGenerator", rename_all = "kebab-case")]
 struct OptionalArguments {
-    /// Absolute path to the file location to be saved
+    /// Absolute path to the file location to be saved, with '--sweep' the directory to save all the files in
     #[structopt(parse(from_os_str), default_value = &DEFAULT_PATH_TO_SAVE_FILE)]
     path_to_save_file: PathBuf,
 
     /// the total number of letters that each generated node name will have
     #[structopt(short, long, default_value = "7")]
-    number_of_letters: u8,
+    number_of_letters: u32,
 
     /// The depth of the JSON tree
     #[structopt(short, long, default_value = "100")]
-    depth: u8,
+    depth: u32,
 
-    /// The number of children each node should have
+    /// The number of children each node should have, with '--target-size' it is the maximal number of children
     #[structopt(short = "m", long, default_value = "6")]
-    number_of_children: u8,
+    number_of_children: u32,
+
+    /// Grow the JSON until it reaches this size (e.g. 512KB, 100MB, 2GB) instead of using '--depth', implies '--stream'
+    #[structopt(short, long, parse(try_from_str = parse_size))]
+    target_size: Option<u64>,
+
+    /// The seed of the random generator, the same seed with the same parameters always generates the same JSON
+    #[structopt(short, long)]
+    seed: Option<u64>,
+
+    /// The relative chance of each leaf type as NULL,BOOL,NUMBER,STRING (e.g. 1,1,1,7 for 70% strings)
+    #[structopt(long, parse(try_from_str = parse_leaf_type_weights))]
+    leaf_type_weights: Option<LeafTypeWeights>,
+
+    /// The minimal length of generated string leaves (in kilobytes with the long string profile)
+    #[structopt(long)]
+    minimal_string_length: Option<usize>,
+
+    /// The maximal length of generated string leaves (in kilobytes with the long string profile)
+    #[structopt(long)]
+    maximal_string_length: Option<usize>,
+
+    /// The minimal value of generated number leaves (negative values are passed as '--minimal-number=-5')
+    #[structopt(long)]
+    minimal_number: Option<f64>,
+
+    /// The maximal value of generated number leaves
+    #[structopt(long)]
+    maximal_number: Option<f64>,
+
+    /// The characters of string leaves: alphabet, escapes (quotes, backslashes and control characters), unicode (multi-byte and non-BMP characters), mixed or long (string lengths in kilobytes)
+    #[structopt(long)]
+    string_profile: Option<StringProfile>,
+
+    /// The kind of number leaves: float (within the minimal and maximal number), integers (within them too), smallIntegers (-1000 to 1000), extremes (i64 and u64 bounds), exponents, highPrecision (-1 to 1 with 16 or more significant digits) or mixed
+    #[structopt(long)]
+    number_profile: Option<NumberProfile>,
+
+    /// The characters of node names (same profiles as '--string-profile'), by default only lowercase letters
+    #[structopt(long)]
+    key_string_profile: Option<StringProfile>,
+
+    /// Write every none ASCII character as a \uXXXX escape (surrogate pairs for non-BMP characters)
+    #[structopt(short = "U", long)]
+    escape_unicode: bool,
+
+    /// The chance (between 0 and 1) of a none leaf node being an object instead of an array (e.g. 1 for objects only)
+    #[structopt(short, long)]
+    object_ratio: Option<f64>,
+
+    /// How many children each none leaf node has: fixed (the '--number-of-children'), uniform:MINIMUM:MAXIMUM or geometric:MINIMUM:MAXIMUM:MEAN
+    #[structopt(short, long, default_value = "fixed")]
+    fan_out: FanOutDistribution,
+
+    /// The chance (between 0 and 1) of a none leaf node being replaced by a leaf, which ends its branch before the full depth
+    #[structopt(short, long, default_value = "0")]
+    early_termination_probability: f64,
+
+    /// Generate the subtrees with this many threads, ignored with '--stream' and '--target-size'
+    #[structopt(short = "T", long, default_value = "1")]
+    number_of_threads: usize,
+
+    /// Redraw a node name until it differs from its siblings, otherwise a repeated name overwrites the previous sibling
+    #[structopt(short, long)]
+    unique_keys: bool,
+
+    /// How the none leaf nodes continue the tree: branching, or deepChain where only the last child of every node continues it (e.g. '-d 10000 --shape deepChain')
+    #[structopt(long, default_value = "branching")]
+    shape: TreeShape,
+
+    /// Plant this JSON value (e.g. '"needle"' or 2000000000) at the '--needle-position', only when building the JSON in memory
+    #[structopt(long, parse(try_from_str = serde_json::from_str))]
+    needle: Option<Value>,
+
+    /// Plant the needle as the name of a node instead of a leaf, the needle has to be a string
+    #[structopt(long)]
+    needle_key: bool,
+
+    /// Where to plant the needle, in the order the nodes are written: first, middle, last or depth:DEPTH (a random node at that depth)
+    #[structopt(long, default_value = "last")]
+    needle_position: NeedlePosition,
+
+    /// Write this many JSONs as JSON Lines (one compact JSON per line), each with its own seed and needle (e.g. '-r 10000 -d 3' for log records)
+    #[structopt(short = "r", long, parse(try_from_str = parse_none_zero_u64))]
+    number_of_records: Option<u64>,
+
+    /// How to lay out the JSON: compact, pretty[:WIDTH[:tabs]] (e.g. 'pretty:4' or 'pretty:1:tabs') or oneKeyPerLine, by default pretty when printing and compact otherwise
+    #[structopt(short = "F", long)]
+    output_format: Option<OutputFormat>,
 
     /// Print the resulting JSON instead of saving it to a file
     #[structopt(short = "P", long)]
     print: bool,
+
+    /// Write the JSON while generating it instead of building it in memory first
+    #[structopt(short = "S", long)]
+    stream: bool,
+
+    /// Generate documents that conform to this JSON Schema (type, enum, const, properties, required, items, minItems, maxItems,
+    /// minLength, maxLength, minimum and maximum) instead of a random tree, the tree parameters are ignored
+    #[structopt(long, parse(from_os_str))]
+    schema: Option<PathBuf>,
+
+    /// Generate a document shaped like a real-world payload instead of a random tree: table, geoJson, nestedConfig, eventLog or socialFeed,
+    /// with '--number-of-children' items (rows, features, top level sections, events or posts), the other tree parameters are ignored
+    #[structopt(long)]
+    preset: Option<Preset>,
+
+    /// Learn the tree parameters, the leaf options, the fan out, the object ratio and the early termination from this sample JSON
+    /// instead of their arguments. With '--target-size', subtrees shaped like the sample are added under the root until the size is reached
+    #[structopt(long, parse(from_os_str))]
+    learn_from: Option<PathBuf>,
+
+    /// Generate a JSON for every combination of the numberOfLetters, depth and numberOfChildren in this spec (each a value, a list or {"from", "to", "step"}),
+    /// and a configs.json for json_tester next to them
+    #[structopt(long, parse(from_os_str))]
+    sweep: Option<PathBuf>,
+}
+/* #endregion */
+
+/// JSON Lines are always compact, a single JSON is printed pretty.
+fn get_default_output_format(options: &OptionalArguments) -> OutputFormat {
+    if options.print && options.number_of_records.is_none() {
+        OutputFormat::Pretty { indent_width: 2, use_tabs: false }
+    } else {
+        OutputFormat::Compact
+    }
+}
+
+fn get_generator_options(options: &OptionalArguments) -> GeneratorOptions {
+    let default_options = GeneratorOptions::default();
+    let default_leaf_options = default_options.leaf;
+    GeneratorOptions {
+        leaf: LeafOptions {
+            type_weights: options.leaf_type_weights.unwrap_or(default_leaf_options.type_weights),
+            minimal_string_length: options.minimal_string_length.unwrap_or(default_leaf_options.minimal_string_length),
+            maximal_string_length: options.maximal_string_length.unwrap_or(default_leaf_options.maximal_string_length),
+            minimal_number: options.minimal_number.unwrap_or(default_leaf_options.minimal_number),
+            maximal_number: options.maximal_number.unwrap_or(default_leaf_options.maximal_number),
+            string_profile: options.string_profile.unwrap_or(default_leaf_options.string_profile),
+            number_profile: options.number_profile.unwrap_or(default_leaf_options.number_profile),
+        },
+        object_ratio: options.object_ratio.unwrap_or(default_options.object_ratio),
+        fan_out: options.fan_out,
+        early_termination_probability: options.early_termination_probability,
+        key_string_profile: options.key_string_profile,
+        number_of_threads: options.number_of_threads,
+        unique_keys: options.unique_keys,
+        shape: options.shape,
+        needle: options.needle.clone().map(|value| Needle {
+            value,
+            is_key: options.needle_key,
+            position: options.needle_position,
+        }),
+        output_format: options.output_format.unwrap_or_else(|| get_default_output_format(options)),
+        preset: options.preset,
+    }
+}
+
+fn get_output_writer(options: &OptionalArguments) -> Result<Box<dyn Write>, Box<dyn Error + Send + Sync>> {
+    let writer: Box<dyn Write> = if options.print {
+        Box::new(BufWriter::new(io::stdout().lock()))
+    } else {
+        Box::new(BufWriter::new(File::create(options.path_to_save_file.as_path())?))
+    };
+
+    if options.escape_unicode {
+        Ok(Box::new(UnicodeEscapingWriter::new(writer)))
+    } else {
+        Ok(writer)
+    }
 }
 
 // Example: clear ; cargo run --bin json_generator -- -d10 -m5 -n8 /mnt/c/Users/Shaked/Documents/Mine/IdeaProjects/PreReactivePoc/junk/hugeJson_numberOfLetters8_depth10_children5.json
 
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let options = OptionalArguments::from_args();
-    let json = Generator::generate_json(ALPHABET, options.number_of_letters, options.depth, options.number_of_children)?;
+    let options = match &options.learn_from {
+        Some(path_to_sample) => learn_from_sample(&ShapeStatistics::read(path_to_sample)?, options)?,
+        None => options,
+    };
+
+    match &options.sweep {
+        Some(path_to_spec) => generate_sweep(&SweepSpec::read(path_to_spec)?, options),
+        None => generate_in_thread(options),
+    }
+}
+
+fn generate_in_thread(options: OptionalArguments) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // A preset has a depth of its own
+    let depth = options.preset.map_or(options.depth, |preset| preset.get_depth());
+    let options = OptionalArguments { depth, ..options };
+
+    // Serializing and dropping a deep JSON are recursive
+    thread::Builder::new()
+        .name(String::from("json_generator"))
+        .stack_size(json_generator::get_stack_size(options.depth))
+        .spawn(move || generate(options))?
+        .join()
+        .map_err(|_error| "The generating thread panicked")?
+}
+
+/// Replaces the arguments of the tree and of the learned generator options with the ones learned from the sample.
+fn learn_from_sample(statistics: &ShapeStatistics, options: OptionalArguments) -> Result<OptionalArguments, Box<dyn Error + Send + Sync>> {
+    if options.sweep.is_some() || options.schema.is_some() || options.preset.is_some() {
+        return Err(Box::from("The parameters can't be learned for a sweep, a schema or a preset"));
+    }
+    let parameters = statistics.learn_parameters();
+    eprintln!("{}", statistics);
+    eprintln!("Learned parameters: {}", serde_json::to_string_pretty(&parameters)?);
 
-    if options.print {
-        println!("{}", serde_json::to_string_pretty(&json)?);
+    let generator_options = parameters.generator_options;
+    let leaf_options = generator_options.leaf;
+    Ok(OptionalArguments {
+        number_of_letters: parameters.number_of_letters,
+        depth: parameters.depth,
+        number_of_children: parameters.number_of_children,
+        leaf_type_weights: Some(leaf_options.type_weights),
+        minimal_string_length: Some(leaf_options.minimal_string_length),
+        maximal_string_length: Some(leaf_options.maximal_string_length),
+        minimal_number: Some(leaf_options.minimal_number),
+        maximal_number: Some(leaf_options.maximal_number),
+        string_profile: Some(leaf_options.string_profile),
+        number_profile: Some(leaf_options.number_profile),
+        key_string_profile: generator_options.key_string_profile,
+        object_ratio: Some(generator_options.object_ratio),
+        fan_out: generator_options.fan_out,
+        early_termination_probability: generator_options.early_termination_probability,
+        ..options
+    })
+}
+
+/// Saves the JSONs of the sweep and their manifests, the configs point at the manifests so json_tester can use them as they are.
+fn generate_sweep(spec: &SweepSpec, options: OptionalArguments) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if options.print || options.target_size.is_some() || options.schema.is_some() {
+        return Err(Box::from("The sweep can't be printed or generated with a target size or a schema"));
+    }
+    let points = spec.get_points()?;
+    fs::create_dir_all(&options.path_to_save_file)?;
+    let directory = fs::canonicalize(&options.path_to_save_file)?;
+
+    let mut configs = Vec::with_capacity(points.len());
+    for point in points {
+        let path_to_save_file = directory.join(point.get_file_name());
+        eprintln!("Generating {}", path_to_save_file.display());
+        generate_in_thread(OptionalArguments {
+            path_to_save_file: path_to_save_file.clone(),
+            number_of_letters: point.number_of_letters,
+            depth: point.depth,
+            number_of_children: point.number_of_children,
+            sweep: None,
+            ..options.clone()
+        })?;
+        configs.push(json!({
+            "name": point.get_name(),
+            "manifest": Manifest::get_manifest_path(&path_to_save_file),
+        }));
+    }
+
+    let path_to_configs = directory.join("configs.json");
+    fs::write(&path_to_configs, serde_json::to_string_pretty(&configs)?)?;
+    eprintln!("Saved the configs of {} JSONs to {}", configs.len(), path_to_configs.display());
+    Ok(())
+}
+
+fn generate(options: OptionalArguments) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let seed = options.seed.unwrap_or_else(randomizer::get_random_seed);
+    let generator_options = get_generator_options(&options);
+    if options.print && options.seed.is_none() {
+        // There is no manifest to keep the drawn seed in
+        eprintln!("Seed {}", seed);
+    }
+
+    if options.target_size.is_some() && options.number_of_records.is_some() {
+        return Err(Box::from("JSON Lines can't be generated with a target size"));
+    }
+    if options.number_of_records.is_some() && !generator_options.output_format.is_compact() {
+        return Err(Box::from(format!("JSON Lines have a compact record per line, they can't be written as {}", generator_options.output_format)));
+    }
+    if options.schema.is_some() && options.preset.is_some() {
+        return Err(Box::from("A preset can't be generated from a schema"));
+    }
+    if options.schema.is_some() && (options.target_size.is_some() || options.stream || generator_options.needle.is_some()) {
+        return Err(Box::from("A schema generates the JSON in memory, without a target size or a needle"));
+    }
+
+    let schema_generator = options.schema.as_deref().map(SchemaGenerator::read).transpose()?;
+    let (depth, number_of_children, node_counts, needle_path) = if let Some(target_size) = options.target_size {
+        let mut writer = get_output_writer(&options)?;
+        let summary = if options.learn_from.is_some() {
+            Generator::write_json_with_growing_root(&mut writer, ALPHABET, options.number_of_letters, options.depth, options.number_of_children, target_size, seed, &generator_options)?
+        } else {
+            Generator::write_json_with_target_size(&mut writer, ALPHABET, options.number_of_letters, options.number_of_children, target_size, seed, &generator_options)?
+        };
+        drop(writer);
+        if options.print {
+            println!();
+        }
+        eprintln!(
+            "Generated {} bytes: depth {}, number of children {}, number of nodes {}",
+            summary.size_in_bytes, summary.depth, summary.number_of_children, summary.number_of_nodes
+        );
+        (summary.depth, summary.number_of_children, NodeCounts { number_of_nodes: summary.number_of_nodes, number_of_leaves: summary.number_of_leaves }, None)
+    } else if options.stream {
+        let mut writer = get_output_writer(&options)?;
+        let summary = match options.number_of_records {
+            Some(number_of_records) => Generator::write_json_lines(&mut writer, ALPHABET, options.number_of_letters, options.depth, options.number_of_children, number_of_records, seed, &generator_options)?,
+            None => Generator::write_json(&mut writer, ALPHABET, options.number_of_letters, options.depth, options.number_of_children, seed, &generator_options)?,
+        };
+        drop(writer);
+        if options.print && options.number_of_records.is_none() {
+            println!();
+        }
+        eprintln!("Generated {} bytes: number of nodes {}, number of leaves {}", summary.size_in_bytes, summary.number_of_nodes, summary.number_of_leaves);
+        (options.depth, options.number_of_children, NodeCounts { number_of_nodes: summary.number_of_nodes, number_of_leaves: summary.number_of_leaves }, None)
+    } else if let Some(schema_generator) = &schema_generator {
+        let generated_jsons = match options.number_of_records {
+            Some(number_of_records) => schema_generator.generate_json_lines(number_of_records, seed),
+            None => vec![schema_generator.generate_json(seed)],
+        };
+        let node_counts = generated_jsons.iter().map(|generated_json| generated_json.node_counts).sum::<NodeCounts>();
+        eprintln!("Generated {} documents: number of nodes {}, number of leaves {}", generated_jsons.len(), node_counts.number_of_nodes, node_counts.number_of_leaves);
+
+        let mut writer = get_output_writer(&options)?;
+        for generated_json in &generated_jsons {
+            output_format::to_writer(&mut writer, &generated_json.json, generator_options.output_format)?;
+            if options.print || options.number_of_records.is_some() {
+                writeln!(writer)?;
+            }
+        }
+        writer.flush()?;
+        (schema_generator.get_depth(), 0, node_counts, None)
+    } else if let Some(number_of_records) = options.number_of_records {
+        let records = Generator::generate_json_lines(ALPHABET, options.number_of_letters, options.depth, options.number_of_children, number_of_records, seed, &generator_options)?;
+        let node_counts = records.iter().map(|record| record.node_counts).sum::<NodeCounts>();
+        eprintln!("Generated {} records: number of nodes {}, number of leaves {}", records.len(), node_counts.number_of_nodes, node_counts.number_of_leaves);
+
+        // A record per line, even when printing
+        let mut writer = get_output_writer(&options)?;
+        for record in &records {
+            serde_json::to_writer(&mut writer, &record.json)?;
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        (options.depth, options.number_of_children, node_counts, None)
     } else {
-        fs::write(options.path_to_save_file.as_path(), serde_json::to_string(&json)?)?;
+        let GeneratedJson { json, node_counts, needle_path } = Generator::generate_json_with_summary(ALPHABET, options.number_of_letters, options.depth, options.number_of_children, seed, &generator_options)?;
+        eprintln!("Generated number of nodes {}, number of leaves {}", node_counts.number_of_nodes, node_counts.number_of_leaves);
+        if let Some(needle_path) = &needle_path {
+            eprintln!("Planted the needle at {}", needle_path);
+        }
+
+        let mut writer = get_output_writer(&options)?;
+        output_format::to_writer(&mut writer, &json, generator_options.output_format)?;
+        if options.print {
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        (options.depth, options.number_of_children, node_counts, needle_path)
+    };
+
+    if !options.print {
+        // Read back, so the checksum covers exactly the bytes on the disk
+        let checksum = Checksum::from_reader(File::open(&options.path_to_save_file)?)?;
+        let manifest = Manifest {
+            file_name: options.path_to_save_file
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or("Invalid file name to save the JSON to")?
+                .to_string(),
+            seed,
+            number_of_letters: if options.schema.is_some() || options.preset.is_some() { 0 } else { options.number_of_letters },
+            characters_poll: String::from(ALPHABET),
+            depth,
+            number_of_children,
+            target_size: options.target_size,
+            size_in_bytes: checksum.get_size_in_bytes(),
+            number_of_nodes: node_counts.number_of_nodes,
+            number_of_leaves: node_counts.number_of_leaves,
+            checksum: checksum.get_checksum(),
+            escape_unicode: options.escape_unicode,
+            needle_path,
+            number_of_records: options.number_of_records,
+            schema_path: options.schema.as_deref().map(fs::canonicalize).transpose()?,
+            schema_checksum: schema_generator.as_ref().map(|schema_generator| schema_generator.get_checksum().to_string()),
+            sample_path: options.learn_from.as_deref().map(fs::canonicalize).transpose()?,
+            generator_options,
+        };
+        manifest.write(&Manifest::get_manifest_path(&options.path_to_save_file))?;
     }
 
     Ok(())
@@ -1,159 +1,1713 @@
 /* #region Imports */
 // Standard
-use std::error::Error;
+use std::{collections::HashSet, error::Error, fmt, fs, io::{self, Write}, iter::Sum, path::{Path, PathBuf}, str::FromStr, sync::Mutex, thread};
 
 // 3rd Party
-use serde_json::{ Value, Map };
+use serde_json::{ Value, Map, ser::Formatter };
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
 
 // Project
-use crate::utils::{ json_type, randomizer };
+use crate::{
+    presets::Preset,
+    search_tree::depth_first_search,
+    utils::{ json_type::{self, ValueNonLeafType, LeafOptions, StringProfile}, randomizer, checksum::Checksum, json_pointer::{self, PathToken}, output_format::{self, JsonFormatter, OutputFormat} },
+};
 /* #endregion */
 
+/// The characters of the node names that the tester generates.
+pub static CHARACTER_POLL: &str = "AaBbCcDdEeFfGgHhIiJjKkLlMmNnOoPpQqRrSsTtUuVvWwXxYyZz!@#$%&";
+
+/* #region Generator Options */
+/// How many children each none leaf node has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FanOutDistribution {
+    /// Exactly the number of children given to the generator.
+    #[default]
+    Fixed,
+    /// Uniformly between the minimum and the maximum (inclusive).
+    Uniform { minimum: u32, maximum: u32 },
+    /// Geometrically from the minimum with the given mean, capped at the maximum.
+    Geometric { minimum: u32, maximum: u32, mean: f64 },
+}
+
+impl FanOutDistribution {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            FanOutDistribution::Fixed => Ok(()),
+            FanOutDistribution::Uniform { minimum, maximum } if minimum > maximum =>
+                Err(format!("Invalid fan out range: {}..={}", minimum, maximum)),
+            FanOutDistribution::Uniform { .. } => Ok(()),
+            FanOutDistribution::Geometric { minimum, maximum, .. } if minimum > maximum =>
+                Err(format!("Invalid fan out range: {}..={}", minimum, maximum)),
+            FanOutDistribution::Geometric { minimum, mean, .. } if !mean.is_finite() || mean < f64::from(minimum) =>
+                Err(format!("The mean of the fan out has to be at least its minimum: {}", mean)),
+            FanOutDistribution::Geometric { .. } => Ok(()),
+        }
+    }
+
+    fn get_maximal_number_of_children(&self, number_of_children: u32) -> u32 {
+        match *self {
+            FanOutDistribution::Fixed => number_of_children,
+            FanOutDistribution::Uniform { maximum, .. } | FanOutDistribution::Geometric { maximum, .. } => maximum,
+        }
+    }
+
+    fn get_random_number_of_children<R: Rng + ?Sized>(&self, random_generator: &mut R, number_of_children: u32) -> u32 {
+        match *self {
+            FanOutDistribution::Fixed => number_of_children,
+            FanOutDistribution::Uniform { minimum, maximum } =>
+                randomizer::get_random_number_in_range(random_generator, minimum..=maximum),
+            FanOutDistribution::Geometric { minimum, maximum, mean } => {
+                let success_probability = 1.0 / (mean - f64::from(minimum) + 1.0);
+                if success_probability >= 1.0 {
+                    return minimum;
+                }
+                let uniform: f64 = random_generator.gen_range(f64::EPSILON..=1.0);
+                let number_of_failures = (uniform.ln() / (1.0 - success_probability).ln()).floor();
+                (f64::from(minimum) + number_of_failures).min(f64::from(maximum)) as u32
+            }
+        }
+    }
+}
+
+/// Formatted as `fixed`, `uniform:MINIMUM:MAXIMUM` or `geometric:MINIMUM:MAXIMUM:MEAN`.
+impl fmt::Display for FanOutDistribution {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FanOutDistribution::Fixed => write!(formatter, "fixed"),
+            FanOutDistribution::Uniform { minimum, maximum } => write!(formatter, "uniform:{}:{}", minimum, maximum),
+            FanOutDistribution::Geometric { minimum, maximum, mean } => write!(formatter, "geometric:{}:{}:{}", minimum, maximum, mean),
+        }
+    }
+}
+
+impl FromStr for FanOutDistribution {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = source.trim().split(':').collect();
+        let fan_out_distribution = match parts[..] {
+            ["fixed"] => FanOutDistribution::Fixed,
+            ["uniform", minimum, maximum] => FanOutDistribution::Uniform { minimum: minimum.parse()?, maximum: maximum.parse()? },
+            ["geometric", minimum, maximum, mean] =>
+                FanOutDistribution::Geometric { minimum: minimum.parse()?, maximum: maximum.parse()?, mean: mean.parse()? },
+            _ => return Err(Box::from(format!("Invalid fan out distribution: {}, expected fixed, uniform:MINIMUM:MAXIMUM or geometric:MINIMUM:MAXIMUM:MEAN", source))),
+        };
+        fan_out_distribution.validate()?;
+        Ok(fan_out_distribution)
+    }
+}
+
+/// How the none leaf nodes continue the tree, selectable as `branching` or `deepChain`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum TreeShape {
+    /// Every child of a none leaf node is a none leaf node until the last level.
+    #[default]
+    Branching,
+    /// Only the last child of a none leaf node continues the tree and the others are leaves,
+    /// so the tree is as deep as the depth but only as wide as the number of children.
+    DeepChain,
+}
+
+/// Where the needle is planted, in the order the nodes are written in the JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NeedlePosition {
+    First,
+    Middle,
+    #[default]
+    Last,
+    /// A random node at the given depth (the children of the root are at depth 1).
+    Depth { depth: u32 },
+}
+
+/// Formatted as `first`, `middle`, `last` or `depth:DEPTH`.
+impl fmt::Display for NeedlePosition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NeedlePosition::First => write!(formatter, "first"),
+            NeedlePosition::Middle => write!(formatter, "middle"),
+            NeedlePosition::Last => write!(formatter, "last"),
+            NeedlePosition::Depth { depth } => write!(formatter, "depth:{}", depth),
+        }
+    }
+}
+
+impl FromStr for NeedlePosition {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = source.trim().split(':').collect();
+        match parts[..] {
+            ["first"] => Ok(NeedlePosition::First),
+            ["middle"] => Ok(NeedlePosition::Middle),
+            ["last"] => Ok(NeedlePosition::Last),
+            ["depth", depth] => Ok(NeedlePosition::Depth { depth: depth.parse()? }),
+            _ => Err(Box::from(format!("Invalid needle position: {}, expected first, middle, last or depth:DEPTH", source))),
+        }
+    }
+}
+
+/// A value that is planted in the generated tree, so searching it ends at a known node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Needle {
+    /// Replaces a leaf, it has to be a value that the generator didn't generate (or else the generation fails).
+    pub value: Value,
+    /// Renames a node instead of replacing a leaf, so the value has to be a string.
+    /// The keys of an object are sorted, so the renamed node might move between its siblings.
+    #[serde(default, rename = "isKey")]
+    pub is_key: bool,
+    #[serde(default)]
+    pub position: NeedlePosition,
+}
+
+impl Needle {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.value.is_array() || self.value.is_object() {
+            return Err(format!("The needle has to be a leaf value: {}", self.value));
+        }
+        if self.is_key && !self.value.is_string() {
+            return Err(format!("The needle key has to be a string: {}", self.value));
+        }
+        Ok(())
+    }
+
+    fn is_candidate(&self, path: &[PathToken], node: &Value) -> bool {
+        let is_in_position = match self.position {
+            NeedlePosition::Depth { depth } => path.len() as u64 == u64::from(depth),
+            _ => true,
+        };
+        let is_needle_type = if self.is_key {
+            matches!(path.last(), Some(PathToken::Key(_)))
+        } else {
+            !path.is_empty() && !node.is_array() && !node.is_object()
+        };
+        is_in_position && is_needle_type
+    }
+}
+
+/// Settings of the generated nodes, the defaults generate uniformly random nodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratorOptions {
+    pub leaf: LeafOptions,
+    /// The chance (between 0 and 1) of a none leaf node (including the root) being an object instead of an array.
+    #[serde(rename = "objectRatio")]
+    pub object_ratio: f64,
+    #[serde(rename = "fanOut")]
+    pub fan_out: FanOutDistribution,
+    /// The chance (between 0 and 1) of a none leaf node being replaced by a leaf, which ends its branch before the full depth.
+    #[serde(rename = "earlyTerminationProbability")]
+    pub early_termination_probability: f64,
+    /// The characters of the node names, instead of the characters poll given to the generator.
+    #[serde(rename = "keyStringProfile")]
+    pub key_string_profile: Option<StringProfile>,
+    /// More than a single thread generates the subtrees in parallel, only when generating the JSON in memory.
+    #[serde(rename = "numberOfThreads")]
+    pub number_of_threads: usize,
+    /// Redraws a node name until it differs from its siblings, otherwise a repeated name overwrites the previous sibling.
+    #[serde(rename = "uniqueKeys")]
+    pub unique_keys: bool,
+    pub shape: TreeShape,
+    /// Only when generating the JSON in memory.
+    pub needle: Option<Needle>,
+    /// How the JSON is written, a JSON that is generated in memory is formatted only when it is serialized.
+    #[serde(rename = "outputFormat")]
+    pub output_format: OutputFormat,
+    /// Generates a document shaped like a real-world payload instead of a random tree, the number of children is its number of items
+    /// and the other tree parameters are ignored.
+    pub preset: Option<Preset>,
+}
+
+impl GeneratorOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.object_ratio) {
+            return Err(format!("The object ratio has to be between 0 and 1: {}", self.object_ratio));
+        }
+        if !(0.0..=1.0).contains(&self.early_termination_probability) {
+            return Err(format!("The early termination probability has to be between 0 and 1: {}", self.early_termination_probability));
+        }
+        if self.number_of_threads == 0 {
+            return Err(String::from("The number of threads has to be none zero"));
+        }
+        if let Some(needle) = &self.needle {
+            needle.validate()?;
+        }
+        self.fan_out.validate()?;
+        self.leaf.validate()
+    }
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions {
+            leaf: LeafOptions::default(),
+            object_ratio: 0.5,
+            fan_out: FanOutDistribution::Fixed,
+            early_termination_probability: 0.0,
+            key_string_profile: None,
+            number_of_threads: 1,
+            unique_keys: false,
+            shape: TreeShape::Branching,
+            needle: None,
+            output_format: OutputFormat::Compact,
+            preset: None,
+        }
+    }
+}
+/* #endregion */
+
+/* #region Stack Size */
+const MINIMAL_STACK_SIZE: usize = 8 << 20;
+const STACK_SIZE_PER_LEVEL: usize = 16 << 10;
+
+/// Parsing, searching, serializing and dropping a JSON are recursive, so a deep JSON needs more stack than the default of a thread.
+pub fn get_stack_size(depth: u32) -> usize {
+    MINIMAL_STACK_SIZE.max((depth as usize).saturating_mul(STACK_SIZE_PER_LEVEL))
+}
+/* #endregion */
+
+/* #region JSON Lines */
+/// Every record of JSON Lines is generated with its own seed, so the records differ but the file is still reproducible.
+/// The seeds are mixed (the SplitMix64 finalizer), so files with adjacent seeds don't share shifted records.
+pub fn get_record_seed(seed: u64, record_index: u64) -> u64 {
+    let mut record_seed = seed ^ record_index.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    record_seed = (record_seed ^ (record_seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    record_seed = (record_seed ^ (record_seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    record_seed ^ (record_seed >> 31)
+}
+
+fn validate_json_lines_format(options: &GeneratorOptions) -> Result<(), String> {
+    if options.output_format.is_compact() {
+        Ok(())
+    } else {
+        Err(format!("JSON Lines have a compact record per line, they can't be written as {}", options.output_format))
+    }
+}
+/* #endregion */
+
+/* #region Manifest */
+const SIZE_UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+/// The size in the largest unit it has at least one of, such as `1.5MB`.
+pub fn format_size(size_in_bytes: u64) -> String {
+    let mut size = size_in_bytes as f64;
+    let mut unit_index = 0;
+    while size >= 1024.0 && unit_index + 1 < SIZE_UNITS.len() {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+    format!("{}{}", (size * 100.0).round() / 100.0, SIZE_UNITS[unit_index])
+}
+
+/// Describes a generated JSON file, saved next to it so the tester doesn't have to repeat its parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The JSON file is expected in the same directory as the manifest.
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    pub seed: u64,
+    #[serde(rename = "numberOfLetters")]
+    pub number_of_letters: u32,
+    /// The characters of the node names.
+    #[serde(rename = "charactersPoll")]
+    pub characters_poll: String,
+    /// With a target size, the depth and the number of children that were picked for it.
+    pub depth: u32,
+    #[serde(rename = "numberOfChildren")]
+    pub number_of_children: u32,
+    #[serde(rename = "targetSize")]
+    pub target_size: Option<u64>,
+    #[serde(rename = "sizeInBytes")]
+    pub size_in_bytes: u64,
+    #[serde(rename = "numberOfNodes")]
+    pub number_of_nodes: u64,
+    #[serde(rename = "numberOfLeaves")]
+    pub number_of_leaves: u64,
+    pub checksum: String,
+    #[serde(rename = "escapeUnicode")]
+    pub escape_unicode: bool,
+    /// The JSON Pointer of the planted needle, only in a single JSON since every record has its own.
+    #[serde(rename = "needlePath")]
+    pub needle_path: Option<String>,
+    /// With JSON Lines, the number of records in the file (one per line), each generated with [get_record_seed].
+    #[serde(default, rename = "numberOfRecords")]
+    pub number_of_records: Option<u64>,
+    /// The JSON Schema the JSON was generated from, instead of the tree parameters (the number of letters and children are 0).
+    #[serde(default, rename = "schemaPath")]
+    pub schema_path: Option<PathBuf>,
+    /// The checksum of the schema when the JSON was generated, see [crate::schema_generator::SchemaGenerator::get_checksum].
+    #[serde(default, rename = "schemaChecksum")]
+    pub schema_checksum: Option<String>,
+    /// The sample JSON the tree parameters and the generator options were learned from.
+    #[serde(default, rename = "samplePath")]
+    pub sample_path: Option<PathBuf>,
+    #[serde(rename = "generatorOptions")]
+    pub generator_options: GeneratorOptions,
+}
+
+impl Manifest {
+    /// The manifest of `path/to/file.json` is `path/to/file.json.manifest.json`.
+    pub fn get_manifest_path(path_to_json: &Path) -> PathBuf {
+        let mut path_to_manifest = path_to_json.as_os_str().to_owned();
+        path_to_manifest.push(".manifest.json");
+        PathBuf::from(path_to_manifest)
+    }
+
+    pub fn read(path_to_manifest: &Path) -> Result<Manifest, Box<dyn Error + Send + Sync>> {
+        let raw_manifest = fs::read_to_string(path_to_manifest)
+            .map_err(|error| format!("Couldn't read the manifest {}: {}", path_to_manifest.display(), error))?;
+        Ok(serde_json::from_str(&raw_manifest)?)
+    }
+
+    pub fn write(&self, path_to_manifest: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+        fs::write(path_to_manifest, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get_path_to_json(&self, path_to_manifest: &Path) -> PathBuf {
+        path_to_manifest.with_file_name(&self.file_name)
+    }
+
+    pub fn get_size(&self) -> String {
+        format_size(self.size_in_bytes)
+    }
+
+    /// Fails when the JSON was changed or regenerated after the manifest was written.
+    pub fn verify(&self, raw_json: &[u8]) -> Result<(), String> {
+        let checksum = Checksum::from_bytes(raw_json);
+        if checksum.get_size_in_bytes() != self.size_in_bytes || checksum.get_checksum() != self.checksum {
+            return Err(format!(
+                "{} doesn't match its manifest: expected {} bytes with checksum {} but found {} bytes with checksum {}",
+                self.file_name, self.size_in_bytes, self.checksum, checksum.get_size_in_bytes(), checksum.get_checksum()
+            ));
+        }
+        Ok(())
+    }
+}
+/* #endregion */
+
+/// The parallel generation generates the first levels until there are at least this many subtrees to split between the threads.
+const PARALLEL_NUMBER_OF_SUBTREES: usize = 256;
+/// The number of nodes in the sample tree used to estimate the average node size.
+const SAMPLE_NUMBER_OF_NODES: f64 = 10_000.0;
+/// The sample tree is cut at this size in case a single level already has too many nodes.
+const SAMPLE_MAXIMAL_SIZE: u64 = 1_000_000;
+/// The deepest full tree that is considered for a target size.
+const MAXIMAL_TARGET_SIZE_DEPTH: u32 = 255;
+/// The full tree picked for a target size is bigger than it by this factor, so estimation errors are still cut to the target size.
+const TARGET_SIZE_MARGIN: f64 = 1.1;
+
+/// A generated JSON tree with what was generated in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedJson {
+    pub json: Value,
+    pub node_counts: NodeCounts,
+    /// The JSON Pointer of the node the needle was planted at.
+    pub needle_path: Option<String>,
+}
+
+/// A generated JSON tree that might not be counted yet, so the counting can be left out of a measurement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncountedJson {
+    pub json: Value,
+    /// Known when the nodes were counted while generating them.
+    pub node_counts: Option<NodeCounts>,
+    pub needle_path: Option<String>,
+}
+
+impl UncountedJson {
+    pub fn count(self) -> GeneratedJson {
+        GeneratedJson {
+            node_counts: self.node_counts.unwrap_or_else(|| NodeCounts::count(&self.json)),
+            json: self.json,
+            needle_path: self.needle_path,
+        }
+    }
+}
+
+/// The number of nodes in a JSON tree, including the root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeCounts {
+    pub number_of_nodes: u64,
+    pub number_of_leaves: u64,
+}
+
+impl NodeCounts {
+    pub fn count(json: &Value) -> NodeCounts {
+        let mut node_counts = NodeCounts::default();
+        let mut nodes = vec![json];
+        while let Some(node) = nodes.pop() {
+            node_counts.number_of_nodes += 1;
+            match node {
+                Value::Array(array) => nodes.extend(array.iter()),
+                Value::Object(map) => nodes.extend(map.values()),
+                _ => node_counts.number_of_leaves += 1,
+            }
+        }
+        node_counts
+    }
+
+    pub fn get_number_of_containers(&self) -> u64 {
+        self.number_of_nodes - self.number_of_leaves
+    }
+
+    fn add_node(&mut self, node: &Value) {
+        self.number_of_nodes += 1;
+        if !node.is_array() && !node.is_object() {
+            self.number_of_leaves += 1;
+        }
+    }
+
+    /// Only for nodes without children yet, such as a sibling that was overwritten by a node with the same name.
+    fn remove_node(&mut self, node: &Value) {
+        self.number_of_nodes -= 1;
+        if !node.is_array() && !node.is_object() {
+            self.number_of_leaves -= 1;
+        }
+    }
+
+    fn add(&mut self, other: NodeCounts) {
+        self.number_of_nodes += other.number_of_nodes;
+        self.number_of_leaves += other.number_of_leaves;
+    }
+}
+
+impl Sum for NodeCounts {
+    fn sum<I: Iterator<Item = NodeCounts>>(iterator: I) -> NodeCounts {
+        let mut node_counts = NodeCounts::default();
+        for other in iterator {
+            node_counts.add(other);
+        }
+        node_counts
+    }
+}
+
+/// The shape and size of a generated JSON tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeSummary {
+    pub depth: u32,
+    pub number_of_children: u32,
+    pub number_of_nodes: u64,
+    pub number_of_leaves: u64,
+    pub size_in_bytes: u64,
+}
+
+/// Counts the bytes written into the inner writer.
+struct CountingWriter<'a, W: Write> {
+    writer: &'a mut W,
+    bytes_written: u64,
+}
+
+impl <'a, W: Write> Write for CountingWriter<'a, W> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let bytes_written = self.writer.write(buffer)?;
+        self.bytes_written += bytes_written as u64;
+        Ok(bytes_written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Returns the number of containers (including the root) and the number of leaves.
+fn get_number_of_nodes_in_full_tree(depth: u32, number_of_children: u32) -> (f64, f64) {
+    let number_of_children = f64::from(number_of_children);
+    let number_of_containers = (0..depth).map(|level| number_of_children.powf(f64::from(level))).sum::<f64>().max(1.0);
+    let number_of_leaves = if depth == 0 { 0.0 } else { number_of_children.powf(f64::from(depth)) };
+    (number_of_containers, number_of_leaves)
+}
+
+/// The name of a child in its parent, owned so the parent can be changed.
+enum ChildName {
+    Index(usize),
+    Key(String),
+}
+
+/// Visits the nodes in the order they are written in the JSON with the path to each of them, until the visitor returns false.
+fn visit_in_document_order<'a, F>(root: &'a Value, mut visitor: F)
+where F: FnMut(&[PathToken<'a>], &'a Value) -> bool {
+    let mut path = vec!();
+    let mut nodes: Vec<(Option<PathToken<'a>>, usize, &'a Value)> = vec![(None, 0, root)];
+    while let Some((token, depth, node)) = nodes.pop() {
+        path.truncate(depth.saturating_sub(1));
+        path.extend(token);
+        if !visitor(&path, node) {
+            return;
+        }
+        match node {
+            Value::Array(array) => nodes.extend(array.iter().enumerate().rev().map(|(index, child)| (Some(PathToken::Index(index)), depth + 1, child))),
+            Value::Object(map) => nodes.extend(map.iter().rev().map(|(key, child)| (Some(PathToken::Key(key.as_str())), depth + 1, child))),
+            _ => {},
+        }
+    }
+}
+
+/// Ends a member of an object or an element of an array.
+fn end_value<W: Write>(formatter: &mut JsonFormatter, writer: &mut W, is_object: bool) -> io::Result<()> {
+    if is_object {
+        formatter.end_object_value(writer)
+    } else {
+        formatter.end_array_value(writer)
+    }
+}
+
+/// A container that [Generator::write_full_tree] has opened but not yet closed.
+struct OpenContainer {
+    is_object: bool,
+    remaining_children: u32,
+    has_children: bool,
+    children_level: u32,
+    /// The names of the children so far, only with unique keys.
+    children_names: HashSet<String>,
+}
+
 #[derive(Debug)]
 pub struct Generator {
     characters_poll: Vec<char>,
     number_of_letters: usize,
-    depth: u8,
-    number_of_children: u8,
+    depth: u32,
+    number_of_children: u32,
+    random_generator: ChaCha8Rng,
+    options: GeneratorOptions,
+    node_counts: NodeCounts,
 }
 
 impl Generator {
-    fn new(characters_poll: &str, number_of_letters: u8, depth: u8, number_of_children: u8) -> Generator {
-        Generator {
-            characters_poll: characters_poll.chars().collect(),
-            number_of_letters: number_of_letters.into(),
+    fn new(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, seed: u64, options: &GeneratorOptions)
+    -> Result<Generator, Box<dyn Error + Send + Sync>> {
+        options.validate()?;
+
+        let characters_poll: Vec<char> = match options.key_string_profile {
+            Some(key_string_profile) => key_string_profile.get_characters().to_vec(),
+            None => characters_poll.chars().collect(),
+        };
+        if options.unique_keys {
+            Generator::validate_unique_keys(&characters_poll, number_of_letters, options.fan_out.get_maximal_number_of_children(number_of_children))?;
+        }
+
+        Ok(Generator {
+            characters_poll,
+            number_of_letters: number_of_letters.try_into()?,
             depth,
-            number_of_children
+            number_of_children,
+            random_generator: randomizer::get_seeded_random_generator(seed),
+            options: options.clone(),
+            node_counts: NodeCounts { number_of_nodes: 1, number_of_leaves: 0 },
+        })
+    }
+
+    /// Otherwise drawing a unique name for the last children never ends.
+    fn validate_unique_keys(characters_poll: &[char], number_of_letters: u32, maximal_number_of_children: u32) -> Result<(), String> {
+        let number_of_characters = characters_poll.iter().collect::<HashSet<_>>().len();
+        let number_of_names = (number_of_characters as f64).powf(f64::from(number_of_letters));
+        if number_of_names < f64::from(maximal_number_of_children) {
+            return Err(format!(
+                "Can't generate {} unique names of {} letters from {} different characters",
+                maximal_number_of_children, number_of_letters, number_of_characters
+            ));
         }
+        Ok(())
+    }
+
+    /// Generates a random JSON tree, the same seed with the same parameters always generates the same tree.
+    /// Every subtree below the first levels is generated from its own seed, so the number of threads doesn't change the tree.
+    pub fn generate_json(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, seed: u64, options: &GeneratorOptions) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        Ok(Generator::generate_json_with_summary(characters_poll, number_of_letters, depth, number_of_children, seed, options)?.json)
+    }
+
+    /// Same as [Generator::generate_json] but also returns the number of generated nodes,
+    /// which is smaller than the parameters imply when the fan out is random, branches terminate early or names repeat,
+    /// and where the needle was planted.
+    pub fn generate_json_with_summary(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, seed: u64, options: &GeneratorOptions)
+    -> Result<GeneratedJson, Box<dyn Error + Send + Sync>> {
+        Ok(Generator::generate_uncounted_json(characters_poll, number_of_letters, depth, number_of_children, seed, options)?.count())
     }
 
-    pub fn generate_json(characters_poll: &str, number_of_letters: u8, depth: u8, number_of_children: u8) -> Result<Value, Box<dyn Error + Send + Sync>> {
-        let generator = Generator::new(characters_poll, number_of_letters, depth, number_of_children);
+    /// Same as [Generator::generate_json_with_summary], but a preset is returned before its nodes are counted.
+    /// The tree is counted while it is generated, so only a preset has to be counted afterwards.
+    pub fn generate_uncounted_json(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, seed: u64, options: &GeneratorOptions)
+    -> Result<UncountedJson, Box<dyn Error + Send + Sync>> {
+        let mut generator = Generator::new(characters_poll, number_of_letters, depth, number_of_children, seed, options)?;
 
-        generator.generate_full_tree()
+        let (mut json, node_counts) = match options.preset {
+            Some(preset) => (preset.generate(&mut generator.random_generator, number_of_children), None),
+            None => {
+                let json = generator.generate_full_tree(options.number_of_threads)?;
+                (json, Some(generator.node_counts))
+            },
+        };
+        let needle_path = match &options.needle {
+            Some(needle) => Some(generator.plant_needle(&mut json, needle)?),
+            None => None,
+        };
+        Ok(UncountedJson { json, node_counts, needle_path })
     }
 
-    fn generate_full_tree(&self) -> Result<Value, Box<dyn Error + Send + Sync>> {
-        let mut root = Map::new();
-        
-        /* #region Edge Cases */
-        if self.depth == 0 {
-            return Ok(Value::Object(root));
-        } else if self.depth == 1 {
-            self.add_leaf_children_to_map(&mut root);
-            return Ok(Value::Object(root));
+    /// Writes a random JSON tree token by token into the writer, only the currently open containers are kept in memory.
+    /// The tree has the same shape as [Generator::generate_json] but the nodes are drawn in a different order,
+    /// so the same seed doesn't generate the same tree in both modes.
+    /// Without unique keys, the summary counts the written nodes, including siblings with repeated names that a parser overwrites.
+    /// A preset is generated in memory and then written, so it is the same JSON in both modes.
+    pub fn write_json<W: Write>(writer: &mut W, characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, seed: u64, options: &GeneratorOptions) -> Result<TreeSummary, Box<dyn Error + Send + Sync>> {
+        if options.needle.is_some() {
+            return Err(Box::from("The needle is planted only when generating the JSON in memory"));
         }
-        /* #endregion */
+        if let Some(preset) = options.preset {
+            let GeneratedJson { json, node_counts, .. } = Generator::generate_json_with_summary(characters_poll, number_of_letters, depth, number_of_children, seed, options)?;
+            let mut writer = CountingWriter { writer, bytes_written: 0 };
+            output_format::to_writer(&mut writer, &json, options.output_format)?;
+            return Ok(TreeSummary {
+                depth: preset.get_depth(),
+                number_of_children,
+                number_of_nodes: node_counts.number_of_nodes,
+                number_of_leaves: node_counts.number_of_leaves,
+                size_in_bytes: writer.bytes_written,
+            });
+        }
+        let mut generator = Generator::new(characters_poll, number_of_letters, depth, number_of_children, seed, options)?;
 
-        /* #region First Level */
-        self.add_none_leaf_children_to_map(&mut root);
-        let mut result =  Value::Object(root);
-        /* #endregion */
+        Ok(generator.write_full_tree(writer, None, None)?.0)
+    }
+
+    /// Writes a random JSON tree that grows until it reaches the target size, the depth and the number of children
+    /// (up to the given maximum) are picked to fit the target size, so the fan out has to be fixed.
+    /// The last subtrees are cut once the target size is reached, so the output is bigger than it by about a single node.
+    pub fn write_json_with_target_size<W: Write>(writer: &mut W, characters_poll: &str, number_of_letters: u32, maximal_number_of_children: u32, target_size: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<TreeSummary, Box<dyn Error + Send + Sync>> {
+        if options.preset.is_some() {
+            return Err(Box::from("A preset can't be generated with a target size"));
+        }
+        if options.fan_out != FanOutDistribution::Fixed || options.early_termination_probability != 0.0 || options.shape != TreeShape::Branching {
+            return Err(Box::from("The target size supports only a fixed number of children without early termination or a deep chain"));
+        }
+        if options.needle.is_some() {
+            return Err(Box::from("The needle is planted only when generating the JSON in memory"));
+        }
+        let (depth, number_of_children) = Generator::find_shape_for_target_size(characters_poll, number_of_letters, maximal_number_of_children, target_size, seed, options)?;
+        let mut generator = Generator::new(characters_poll, number_of_letters, depth, number_of_children, seed, options)?;
+
+        Ok(generator.write_full_tree(writer, Some(target_size), None)?.0)
+    }
+
+    /// Writes random subtrees with the given depth and number of children under the root until the JSON reaches the target size,
+    /// so unlike [Generator::write_json_with_target_size] the shape stays the same at any size, with any fan out and early termination.
+    /// The last subtree is cut once the target size is reached.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_json_with_growing_root<W: Write>(writer: &mut W, characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, target_size: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<TreeSummary, Box<dyn Error + Send + Sync>> {
+        if depth == 0 {
+            return Err(Box::from("The root can't grow without children"));
+        }
+        if options.preset.is_some() {
+            return Err(Box::from("A preset can't be generated with a target size"));
+        }
+        if options.unique_keys || options.shape != TreeShape::Branching {
+            return Err(Box::from("The growing root supports neither unique keys nor a deep chain"));
+        }
+        if options.needle.is_some() {
+            return Err(Box::from("The needle is planted only when generating the JSON in memory"));
+        }
+        let mut generator = Generator::new(characters_poll, number_of_letters, depth, number_of_children, seed, options)?;
+
+        Ok(generator.write_full_tree(writer, Some(target_size), Some(u32::MAX))?.0)
+    }
+
+    /// Generates the records of JSON Lines, every record is a whole tree with its own needle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_json_lines(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<Vec<GeneratedJson>, Box<dyn Error + Send + Sync>> {
+        let records = Generator::generate_uncounted_json_lines(characters_poll, number_of_letters, depth, number_of_children, number_of_records, seed, options)?;
+        Ok(records.into_iter().map(UncountedJson::count).collect())
+    }
+
+    /// Same as [Generator::generate_json_lines], with the records of [Generator::generate_uncounted_json].
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_uncounted_json_lines(characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<Vec<UncountedJson>, Box<dyn Error + Send + Sync>> {
+        validate_json_lines_format(options)?;
+        (0..number_of_records)
+            .map(|record_index| Generator::generate_uncounted_json(characters_poll, number_of_letters, depth, number_of_children, get_record_seed(seed, record_index), options))
+            .collect()
+    }
+
+    /// Writes the records of JSON Lines token by token, the same as [Generator::write_json] followed by a new line for every record.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_json_lines<W: Write>(writer: &mut W, characters_poll: &str, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<TreeSummary, Box<dyn Error + Send + Sync>> {
+        validate_json_lines_format(options)?;
+        let mut summary = TreeSummary { depth, number_of_children, number_of_nodes: 0, number_of_leaves: 0, size_in_bytes: 0 };
+        for record_index in 0..number_of_records {
+            let record_summary = Generator::write_json(writer, characters_poll, number_of_letters, depth, number_of_children, get_record_seed(seed, record_index), options)?;
+            writer.write_all(b"\n")?;
+            summary.number_of_nodes += record_summary.number_of_nodes;
+            summary.number_of_leaves += record_summary.number_of_leaves;
+            summary.size_in_bytes += record_summary.size_in_bytes + 1;
+        }
+        Ok(summary)
+    }
+
+    /// Same as [Generator::write_json_with_target_size] but returns the generated tree.
+    pub fn generate_json_with_target_size(characters_poll: &str, number_of_letters: u32, maximal_number_of_children: u32, target_size: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<(Value, TreeSummary), Box<dyn Error + Send + Sync>> {
+        let mut buffer = Vec::with_capacity(target_size.try_into()?);
+        let summary = Generator::write_json_with_target_size(&mut buffer, characters_poll, number_of_letters, maximal_number_of_children, target_size, seed, options)?;
+
+        Ok((serde_json::from_slice(&buffer)?, summary))
+    }
+
+    /// Returns the summary of the written tree and the total size of its leaves (including their names).
+    /// The root number of children replaces the random one of the root.
+    fn write_full_tree<W: Write>(&mut self, writer: &mut W, target_size: Option<u64>, root_number_of_children: Option<u32>) -> Result<(TreeSummary, u64), Box<dyn Error + Send + Sync>> {
+        let mut writer = CountingWriter { writer, bytes_written: 0 };
+        let mut formatter = JsonFormatter::new(self.options.output_format);
+        let mut number_of_nodes = 1;
+        let mut number_of_leaves = 0;
+        let mut leaves_size_in_bytes = 0;
+        let is_root_object = match json_type::get_random_none_leaf_json_type(&mut self.random_generator, self.options.object_ratio) {
+            ValueNonLeafType::Object => true,
+            ValueNonLeafType::Array => false,
+        };
+        if is_root_object {
+            formatter.begin_object(&mut writer)?;
+        } else {
+            formatter.begin_array(&mut writer)?;
+        }
+        let root_number_of_children = match root_number_of_children {
+            _ if self.depth == 0 => 0,
+            Some(root_number_of_children) => root_number_of_children,
+            None => self.get_random_number_of_children(),
+        };
+        let mut open_containers = vec![OpenContainer {
+            is_object: is_root_object,
+            remaining_children: root_number_of_children,
+            has_children: false,
+            children_level: 1,
+            children_names: HashSet::new(),
+        }];
+
+        while let Some(current_container) = open_containers.last_mut() {
+            if target_size.is_some_and(|target_size| writer.bytes_written >= target_size) {
+                current_container.remaining_children = 0;
+            }
 
-        /* #region Middle Levels */
-        let mut current_nodes: Vec<&mut Value> = vec![&mut result];
-        let mut next_level_nodes: Vec<&mut Value> = vec!();
-        let last_level = self.depth - 1;
-
-        for _level in 1..last_level {
-            while let Some(current_node) = current_nodes.pop() {
-                match current_node {
-                    Value::Array(current_node_array) =>
-                        self.add_none_leaf_children(current_node_array.iter_mut(), &mut next_level_nodes)?,
-                    Value::Object(current_node_object) =>
-                        self.add_none_leaf_children(current_node_object.values_mut(), &mut next_level_nodes)?,
-                    _ => return Err(Box::from(format!("Invalid current node type: {}", current_node)))
+            if current_container.remaining_children == 0 {
+                if current_container.is_object {
+                    formatter.end_object(&mut writer)?;
+                } else {
+                    formatter.end_array(&mut writer)?;
                 }
+                open_containers.pop();
+                if let Some(parent_container) = open_containers.last() {
+                    end_value(&mut formatter, &mut writer, parent_container.is_object)?;
+                }
+                continue;
             }
 
-            current_nodes = next_level_nodes;
-            next_level_nodes = vec!();
+            let child_start = writer.bytes_written;
+            let is_first_child = !current_container.has_children;
+            current_container.remaining_children -= 1;
+            current_container.has_children = true;
+            let is_object = current_container.is_object;
+            let children_level = current_container.children_level;
+            let is_chain_leaf = self.options.shape == TreeShape::DeepChain && current_container.remaining_children > 0;
+            number_of_nodes += 1;
+
+            if is_object {
+                let child_node_name = if self.options.unique_keys {
+                    let children_names = &mut current_container.children_names;
+                    let child_node_name = self.get_random_unique_node_name(|name| children_names.contains(name));
+                    children_names.insert(child_node_name.clone());
+                    child_node_name
+                } else {
+                    self.get_random_node_name()
+                };
+                formatter.begin_object_key(&mut writer, is_first_child)?;
+                serde_json::to_writer(&mut writer, &child_node_name)?;
+                formatter.end_object_key(&mut writer)?;
+                formatter.begin_object_value(&mut writer)?;
+            } else {
+                formatter.begin_array_value(&mut writer, is_first_child)?;
+            }
+
+            if children_level == self.depth || is_chain_leaf || self.is_branch_terminated() {
+                let child_node = json_type::get_random_leaf_json(&mut self.random_generator, &self.options.leaf);
+                serde_json::to_writer(&mut writer, &child_node)?;
+                end_value(&mut formatter, &mut writer, is_object)?;
+                number_of_leaves += 1;
+                leaves_size_in_bytes += writer.bytes_written - child_start;
+            } else {
+                let is_object = match json_type::get_random_none_leaf_json_type(&mut self.random_generator, self.options.object_ratio) {
+                    ValueNonLeafType::Array => false,
+                    ValueNonLeafType::Object => true,
+                };
+                if is_object {
+                    formatter.begin_object(&mut writer)?;
+                } else {
+                    formatter.begin_array(&mut writer)?;
+                }
+                open_containers.push(OpenContainer {
+                    is_object,
+                    remaining_children: self.get_random_number_of_children(),
+                    has_children: false,
+                    children_level: children_level + 1,
+                    children_names: HashSet::new(),
+                });
+            }
+        }
+
+        writer.flush()?;
+        let summary = TreeSummary {
+            depth: self.depth,
+            number_of_children: self.number_of_children,
+            number_of_nodes,
+            number_of_leaves,
+            size_in_bytes: writer.bytes_written,
+        };
+        Ok((summary, leaves_size_in_bytes))
+    }
+
+    /// Picks the depth and the number of children whose full tree is the smallest one that is still bigger than the target size,
+    /// based on the average leaf and container sizes of a sample tree.
+    fn find_shape_for_target_size(characters_poll: &str, number_of_letters: u32, maximal_number_of_children: u32, target_size: u64, seed: u64, options: &GeneratorOptions)
+    -> Result<(u32, u32), Box<dyn Error + Send + Sync>> {
+        let sample_depth = (1..=MAXIMAL_TARGET_SIZE_DEPTH)
+            .find(|&depth| {
+                let (number_of_containers, number_of_leaves) = get_number_of_nodes_in_full_tree(depth, maximal_number_of_children);
+                number_of_containers + number_of_leaves >= SAMPLE_NUMBER_OF_NODES
+            })
+            .unwrap_or(MAXIMAL_TARGET_SIZE_DEPTH);
+        let (sample, sample_leaves_size_in_bytes) = Generator::new(characters_poll, number_of_letters, sample_depth, maximal_number_of_children, seed, options)?
+            .write_full_tree(&mut io::sink(), Some(SAMPLE_MAXIMAL_SIZE), None)?;
+        let sample_number_of_containers = sample.number_of_nodes - sample.number_of_leaves;
+        let average_container_size = (sample.size_in_bytes - sample_leaves_size_in_bytes) as f64 / sample_number_of_containers as f64;
+        let average_leaf_size = if sample.number_of_leaves == 0 { 0.0 } else { sample_leaves_size_in_bytes as f64 / sample.number_of_leaves as f64 };
+        let get_full_tree_size = |depth, number_of_children| {
+            let (number_of_containers, number_of_leaves) = get_number_of_nodes_in_full_tree(depth, number_of_children);
+            number_of_containers * average_container_size + number_of_leaves * average_leaf_size
+        };
+        let minimal_size = target_size as f64 * TARGET_SIZE_MARGIN;
+
+        let minimal_number_of_children = maximal_number_of_children.min(2);
+        (minimal_number_of_children..=maximal_number_of_children)
+            .filter_map(|number_of_children| (1..=MAXIMAL_TARGET_SIZE_DEPTH)
+                .map(|depth| (depth, number_of_children, get_full_tree_size(depth, number_of_children)))
+                .find(|&(_depth, _number_of_children, size)| size >= minimal_size))
+            .min_by(|(_, _, first_size), (_, _, second_size)| first_size.total_cmp(second_size))
+            .map(|(depth, number_of_children, _size)| (depth, number_of_children))
+            .ok_or_else(|| Box::from(format!("Can't generate a JSON tree of {} bytes with up to {} children per node", target_size, maximal_number_of_children)))
+    }
+
+    /// Generates the first levels until there are enough subtrees to split between the threads, then every subtree from its own seed.
+    /// A single thread generates the subtrees in order on the current thread, from the same seeds.
+    fn generate_full_tree(&mut self, number_of_threads: usize) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let mut result = json_type::get_random_none_leaf_json(&mut self.random_generator, self.options.object_ratio);
+
+        /* #region First Levels */
+        let mut subtrees: Vec<&mut Value> = vec![&mut result];
+        let mut subtrees_depth = self.depth;
+        while subtrees_depth > 1 && subtrees.len() < PARALLEL_NUMBER_OF_SUBTREES {
+            let mut next_level_subtrees = vec!();
+            for subtree in subtrees {
+                self.add_children(subtree, subtrees_depth, &mut next_level_subtrees);
+            }
+            subtrees = next_level_subtrees;
+            subtrees_depth -= 1;
         }
         /* #endregion */
 
-        /* #region Last Level */
-        while let Some(current_node) = current_nodes.pop() {
-            match current_node {
-                Value::Array(current_node_array) =>
-                    self.add_leaf_children(current_node_array.iter_mut())?,
-                Value::Object(current_node_object) =>
-                    self.add_leaf_children(current_node_object.values_mut())?,
-                _ => return Err(Box::from(format!("Invalid current node type: {}", current_node)))
+        /* #region Subtrees */
+        let subtrees: Vec<(&mut Value, u64)> = subtrees
+            .into_iter()
+            .map(|subtree| (subtree, self.random_generator.gen()))
+            .collect();
+        if number_of_threads == 1 {
+            for (subtree, seed) in subtrees {
+                let mut subtree_generator = self.get_subtree_generator(seed);
+                subtree_generator.fill_subtree(subtree, subtrees_depth);
+                self.node_counts.add(subtree_generator.node_counts);
             }
+            return Ok(result);
         }
+        let subtrees = Mutex::new(subtrees.into_iter());
+        let generator = &*self;
+
+        let subtrees_node_counts = thread::scope(|scope| {
+            let thread_handlers: Vec<_> = (0..number_of_threads)
+                .map(|_thread_index| scope.spawn(|| {
+                    let mut thread_node_counts = NodeCounts::default();
+                    loop {
+                        let next_subtree = subtrees.lock().expect("Another generating thread panicked").next();
+                        let Some((subtree, seed)) = next_subtree else {
+                            break thread_node_counts;
+                        };
+                        let mut subtree_generator = generator.get_subtree_generator(seed);
+                        subtree_generator.fill_subtree(subtree, subtrees_depth);
+                        thread_node_counts.add(subtree_generator.node_counts);
+                    }
+                }))
+                .collect();
+
+            let mut subtrees_node_counts = NodeCounts::default();
+            for thread_handler in thread_handlers {
+                subtrees_node_counts.add(thread_handler.join().map_err(|_error| "A generating thread panicked")?);
+            }
+            Ok::<NodeCounts, Box<dyn Error + Send + Sync>>(subtrees_node_counts)
+        })?;
+        self.node_counts.add(subtrees_node_counts);
         /* #endregion */
 
         Ok(result)
     }
 
+    fn get_subtree_generator(&self, seed: u64) -> Generator {
+        Generator {
+            characters_poll: self.characters_poll.clone(),
+            number_of_letters: self.number_of_letters,
+            depth: self.depth,
+            number_of_children: self.number_of_children,
+            random_generator: randomizer::get_seeded_random_generator(seed),
+            options: self.options.clone(),
+            // The subtree root is counted by the generator that added it
+            node_counts: NodeCounts::default(),
+        }
+    }
+
+    /// Adds the given number of levels under the empty container.
+    fn fill_subtree(&mut self, root: &mut Value, depth: u32) {
+        let mut current_nodes: Vec<&mut Value> = vec![root];
+        for remaining_depth in (1..=depth).rev() {
+            let mut next_level_nodes = vec!();
+            for current_node in current_nodes {
+                self.add_children(current_node, remaining_depth, &mut next_level_nodes);
+            }
+            current_nodes = next_level_nodes;
+        }
+    }
+
+    /// Adds children to the empty container, which are leaves when the remaining depth is 1, and pushes the none leaf ones.
+    fn add_children<'a>(&mut self, node: &'a mut Value, remaining_depth: u32, next_level_nodes: &mut Vec<&'a mut Value>) {
+        let is_last_level = remaining_depth == 1;
+        match node {
+            Value::Array(array) if is_last_level => self.add_leaf_children_to_array(array),
+            Value::Object(map) if is_last_level => self.add_leaf_children_to_map(map),
+            Value::Array(array) => {
+                self.add_none_leaf_children_to_array(array);
+                next_level_nodes.extend(array.iter_mut().filter(|child| child.is_array() || child.is_object()));
+            },
+            Value::Object(map) => {
+                self.add_none_leaf_children_to_map(map);
+                next_level_nodes.extend(map.values_mut().filter(|child| child.is_array() || child.is_object()));
+            },
+            // The branch was terminated early
+            _ => {}
+        }
+    }
+
+    /* #region Needle */
+    /// Replaces a leaf (or renames a node) with the needle and returns the JSON Pointer of it.
+    fn plant_needle(&mut self, json: &mut Value, needle: &Needle) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let mut number_of_candidates: u64 = 0;
+        visit_in_document_order(json, |path, node| {
+            if needle.is_candidate(path, node) {
+                number_of_candidates += 1;
+            }
+            true
+        });
+        if number_of_candidates == 0 {
+            return Err(Box::from(format!("There is no node to plant the needle at {}", needle.position)));
+        }
+
+        let candidate_index = match needle.position {
+            NeedlePosition::First => 0,
+            NeedlePosition::Middle => number_of_candidates / 2,
+            NeedlePosition::Last => number_of_candidates - 1,
+            NeedlePosition::Depth { .. } => randomizer::get_random_number_in_range(&mut self.random_generator, 0..number_of_candidates),
+        };
+        let mut current_candidate_index = 0;
+        let mut needle_location = None;
+        visit_in_document_order(json, |path, node| {
+            if !needle.is_candidate(path, node) {
+                return true;
+            }
+            if current_candidate_index < candidate_index {
+                current_candidate_index += 1;
+                return true;
+            }
+            if let Some((token, parent_path)) = path.split_last() {
+                let child_name = match *token {
+                    PathToken::Index(index) => ChildName::Index(index),
+                    PathToken::Key(key) => ChildName::Key(key.to_string()),
+                };
+                needle_location = Some((json_pointer::to_json_pointer(parent_path), child_name));
+            }
+            false
+        });
+
+        let (parent_path, child_name) = needle_location.ok_or("The needle can't replace the root")?;
+        let parent = json.pointer_mut(&parent_path).ok_or("The needle's parent disappeared")?;
+        let needle_token = match (parent, child_name) {
+            (Value::Object(map), ChildName::Key(key)) if needle.is_key => {
+                let needle_key = needle.value.as_str().ok_or("The needle key has to be a string")?;
+                // Renaming onto an existing key would overwrite the sibling with its whole subtree
+                if map.contains_key(needle_key) {
+                    return Err(Box::from(format!("The parent {} already has the needle key {}", parent_path, needle_key)));
+                }
+                let node = map.remove(&key).ok_or("The needle's node disappeared")?;
+                map.insert(needle_key.to_string(), node);
+                json_pointer::to_json_pointer(&[PathToken::Key(needle_key)])
+            },
+            (Value::Object(map), ChildName::Key(key)) => {
+                *map.get_mut(&key).ok_or("The needle's node disappeared")? = needle.value.clone();
+                json_pointer::to_json_pointer(&[PathToken::Key(&key)])
+            },
+            (Value::Array(array), ChildName::Index(index)) => {
+                *array.get_mut(index).ok_or("The needle's node disappeared")? = needle.value.clone();
+                json_pointer::to_json_pointer(&[PathToken::Index(index)])
+            },
+            _ => return Err(Box::from("The needle's parent changed its type")),
+        };
+
+        // A needle that the generator generated too would be found before the planted one
+        let number_of_needles = depth_first_search::count(json, &needle.value);
+        if number_of_needles != 1 {
+            return Err(Box::from(format!(
+                "The needle {} is in the generated JSON {} times, it has to be a value that the generator doesn't generate", needle.value, number_of_needles
+            )));
+        }
+        Ok(parent_path + &needle_token)
+    }
+    /* #endregion */
+
     /* #region Helper methods */
-    fn get_random_node_character(&self) -> &char {
-        randomizer::get_random_value_from_array(&self.characters_poll)
+    fn get_random_node_character(&mut self) -> char {
+        *randomizer::get_random_value_from_array(&mut self.random_generator, &self.characters_poll)
     }
 
-    fn get_random_node_name(&self) -> String {
+    fn get_random_node_name(&mut self) -> String {
         let mut string_builder = String::with_capacity(self.number_of_letters);
         for _count in 0..self.number_of_letters {
-            string_builder.push(*self.get_random_node_character());
+            string_builder.push(self.get_random_node_character());
         }
         string_builder
     }
 
-    fn add_none_leaf_children_to_array(&self, array: &mut Vec<Value>) {
-        for _node_count in 0..self.number_of_children {
-            let child_node = json_type::get_random_none_leaf_json();
+    /// With unique keys, draws names until one isn't taken by a sibling.
+    fn get_random_unique_node_name<F: Fn(&str) -> bool>(&mut self, is_taken: F) -> String {
+        loop {
+            let node_name = self.get_random_node_name();
+            if !is_taken(&node_name) {
+                return node_name;
+            }
+        }
+    }
+
+    fn get_random_child_node_name(&mut self, object: &Map<String, Value>) -> String {
+        if self.options.unique_keys {
+            self.get_random_unique_node_name(|name| object.contains_key(name))
+        } else {
+            self.get_random_node_name()
+        }
+    }
+
+    fn insert_child_to_map(&mut self, object: &mut Map<String, Value>, child_node_name: String, child_node: Value) {
+        self.node_counts.add_node(&child_node);
+        if let Some(overwritten_node) = object.insert(child_node_name, child_node) {
+            self.node_counts.remove_node(&overwritten_node);
+        }
+    }
+
+    fn get_random_number_of_children(&mut self) -> u32 {
+        self.options.fan_out.get_random_number_of_children(&mut self.random_generator, self.number_of_children)
+    }
+
+    fn is_branch_terminated(&mut self) -> bool {
+        self.options.early_termination_probability > 0.0 && self.random_generator.gen_bool(self.options.early_termination_probability)
+    }
+
+    /// Might be a leaf if the branch is terminated early.
+    fn get_random_none_leaf_child(&mut self) -> Value {
+        if self.is_branch_terminated() {
+            json_type::get_random_leaf_json(&mut self.random_generator, &self.options.leaf)
+        } else {
+            json_type::get_random_none_leaf_json(&mut self.random_generator, self.options.object_ratio)
+        }
+    }
+
+    /// In a deep chain, only the last child continues the tree.
+    fn is_chain_leaf(&self, child_index: u32, number_of_children: u32) -> bool {
+        self.options.shape == TreeShape::DeepChain && child_index + 1 < number_of_children
+    }
+
+    fn get_random_child(&mut self, child_index: u32, number_of_children: u32) -> Value {
+        if self.is_chain_leaf(child_index, number_of_children) {
+            json_type::get_random_leaf_json(&mut self.random_generator, &self.options.leaf)
+        } else {
+            self.get_random_none_leaf_child()
+        }
+    }
+
+    fn add_none_leaf_children_to_array(&mut self, array: &mut Vec<Value>) {
+        let number_of_children = self.get_random_number_of_children();
+        for child_index in 0..number_of_children {
+            let child_node = self.get_random_child(child_index, number_of_children);
+            self.node_counts.add_node(&child_node);
             array.push(child_node);
         }
     }
     
-    fn add_none_leaf_children_to_map(&self, object: &mut Map<String, Value>) {
-        for _node_count in 0..self.number_of_children {
-            let child_node_name = self.get_random_node_name();
-            let child_node = json_type::get_random_none_leaf_json();
-            object.insert(child_node_name, child_node);
+    fn add_none_leaf_children_to_map(&mut self, object: &mut Map<String, Value>) {
+        let number_of_children = self.get_random_number_of_children();
+        for child_index in 0..number_of_children {
+            let child_node_name = self.get_random_child_node_name(object);
+            let child_node = self.get_random_child(child_index, number_of_children);
+            self.insert_child_to_map(object, child_node_name, child_node);
+        }
+    }
+
+    fn add_leaf_children_to_array(&mut self, array: &mut Vec<Value>) {
+        for _node_count in 0..self.get_random_number_of_children() {
+            let child_node = json_type::get_random_leaf_json(&mut self.random_generator, &self.options.leaf);
+            self.node_counts.add_node(&child_node);
+            array.push(child_node);
+        }
+    }
+
+    fn add_leaf_children_to_map(&mut self, object: &mut Map<String, Value>) {
+        for _node_count in 0..self.get_random_number_of_children() {
+            let child_node_name = self.get_random_child_node_name(object);
+            let child_node = json_type::get_random_leaf_json(&mut self.random_generator, &self.options.leaf);
+            self.insert_child_to_map(object, child_node_name, child_node);
+        }
+    }
+
+    /* #endregion */
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+    // Project
+    use super::*;
+    /* #endregion */
+
+    const CHARACTERS_POLL: &str = "abcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn same_seed_generates_same_tree() {
+        let first = Generator::generate_json(CHARACTERS_POLL, 5, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        let second = Generator::generate_json(CHARACTERS_POLL, 5, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn different_seed_generates_different_tree() {
+        let first = Generator::generate_json(CHARACTERS_POLL, 5, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        let second = Generator::generate_json(CHARACTERS_POLL, 5, 4, 3, 43, &GeneratorOptions::default()).unwrap();
+        assert_ne!(first, second);
+    }
+
+    fn assert_full_tree(node: &Value, level: u32, depth: u32, number_of_children: usize) {
+        let children: Vec<&Value> = match node {
+            Value::Array(array) => array.iter().collect(),
+            Value::Object(map) => map.values().collect(),
+            leaf => {
+                assert_eq!(level, depth, "Found leaf {} before the last level", leaf);
+                return;
+            }
+        };
+        assert!(level < depth, "Found container at level {} of a tree with depth {}", level, depth);
+        assert_eq!(children.len(), number_of_children);
+        for child in children {
+            assert_full_tree(child, level + 1, depth, number_of_children);
         }
     }
 
-    fn add_none_leaf_children<'a, 'b, I>(&self, iterator: I, next_level_nodes: &'b mut Vec<&'a mut Value>) -> Result<(), Box<dyn Error + Send + Sync>>
-    where I: Iterator<Item = &'a mut Value> {
-        for next_level_node in iterator {
-            match next_level_node {
-                Value::Array(array) => self.add_none_leaf_children_to_array(array),
-                Value::Object(map) => self.add_none_leaf_children_to_map(map),
-                _ => return Err(Box::from(format!("Invalid child node type: {}", next_level_node)))
+    #[test]
+    fn write_json_generates_full_tree() {
+        let mut buffer = vec!();
+        Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        let json: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_full_tree(&json, 0, 4, 3);
+    }
+
+    #[test]
+    fn write_json_edge_cases() {
+        for (object_ratio, expected_json) in [(1.0, b"{}"), (0.0, b"[]")] {
+            let mut buffer = vec!();
+            let options = GeneratorOptions { object_ratio, ..GeneratorOptions::default() };
+            Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 0, 3, 42, &options).unwrap();
+            assert_eq!(&buffer, expected_json);
+        }
+
+        let mut buffer = vec!();
+        Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 1, 3, 42, &GeneratorOptions::default()).unwrap();
+        let json: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_full_tree(&json, 0, 1, 3);
+    }
+
+    fn count_containers(node: &Value, number_of_arrays: &mut usize, number_of_objects: &mut usize) {
+        match node {
+            Value::Array(array) => {
+                *number_of_arrays += 1;
+                array.iter().for_each(|child| count_containers(child, number_of_arrays, number_of_objects));
+            },
+            Value::Object(map) => {
+                *number_of_objects += 1;
+                map.values().for_each(|child| count_containers(child, number_of_arrays, number_of_objects));
+            },
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn object_ratio_controls_container_types() {
+        for (object_ratio, expect_arrays, expect_objects) in [(0.0, true, false), (1.0, false, true)] {
+            let options = GeneratorOptions { object_ratio, ..GeneratorOptions::default() };
+            let json = Generator::generate_json(CHARACTERS_POLL, 16, 4, 3, 42, &options).unwrap();
+            let mut buffer = vec!();
+            Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 4, 3, 42, &options).unwrap();
+            let streamed_json: Value = serde_json::from_slice(&buffer).unwrap();
+
+            for json in [json, streamed_json] {
+                let (mut number_of_arrays, mut number_of_objects) = (0, 0);
+                count_containers(&json, &mut number_of_arrays, &mut number_of_objects);
+                assert_eq!(number_of_arrays > 0, expect_arrays, "object ratio {}", object_ratio);
+                assert_eq!(number_of_objects > 0, expect_objects, "object ratio {}", object_ratio);
             }
-            next_level_nodes.push(next_level_node);
         }
 
-        Ok(())
+        let options = GeneratorOptions { object_ratio: 1.5, ..GeneratorOptions::default() };
+        assert!(Generator::generate_json(CHARACTERS_POLL, 16, 4, 3, 42, &options).is_err());
     }
 
-    fn add_leaf_children_to_array(&self, array: &mut Vec<Value>) {
-        for _node_count in 0..self.number_of_children {
-            let child_node = json_type::get_random_leaf_json();
-            array.push(child_node);
+    /// Returns whether any branch ended before the full depth.
+    fn assert_ragged_tree(node: &Value, level: u32, depth: u32, minimal_number_of_children: usize, maximal_number_of_children: usize) -> bool {
+        let children: Vec<&Value> = match node {
+            Value::Array(array) => array.iter().collect(),
+            Value::Object(map) => map.values().collect(),
+            _ => {
+                assert!(level > 0 && level <= depth, "Found leaf at level {} of a tree with depth {}", level, depth);
+                return level < depth;
+            }
+        };
+        assert!(level < depth, "Found container at level {} of a tree with depth {}", level, depth);
+        assert!(children.len() <= maximal_number_of_children, "Too many children: {}", children.len());
+        if let Value::Array(_) = node {
+            assert!(children.len() >= minimal_number_of_children, "Too few children: {}", children.len());
+        }
+        let mut is_ragged = false;
+        for child in children {
+            is_ragged |= assert_ragged_tree(child, level + 1, depth, minimal_number_of_children, maximal_number_of_children);
+        }
+        is_ragged
+    }
+
+    #[test]
+    fn ragged_tree_shape() {
+        let options = GeneratorOptions {
+            fan_out: FanOutDistribution::Uniform { minimum: 1, maximum: 5 },
+            early_termination_probability: 0.3,
+            ..GeneratorOptions::default()
+        };
+        let json = Generator::generate_json(CHARACTERS_POLL, 16, 6, 3, 42, &options).unwrap();
+        assert!(assert_ragged_tree(&json, 0, 6, 1, 5), "Expected a ragged tree");
+        let mut buffer = vec!();
+        Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 6, 3, 42, &options).unwrap();
+        let json: Value = serde_json::from_slice(&buffer).unwrap();
+        assert!(assert_ragged_tree(&json, 0, 6, 1, 5), "Expected a ragged tree");
+
+        let options = GeneratorOptions {
+            fan_out: FanOutDistribution::Geometric { minimum: 0, maximum: 8, mean: 2.0 },
+            ..GeneratorOptions::default()
+        };
+        let json = Generator::generate_json(CHARACTERS_POLL, 16, 6, 3, 42, &options).unwrap();
+        assert_ragged_tree(&json, 0, 6, 0, 8);
+    }
+
+    #[test]
+    fn geometric_fan_out_mean() {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        let fan_out = FanOutDistribution::Geometric { minimum: 1, maximum: u32::MAX, mean: 4.0 };
+        let total: u64 = (0..10_000).map(|_count| u64::from(fan_out.get_random_number_of_children(&mut random_generator, 0))).sum();
+        let mean = total as f64 / 10_000.0;
+        assert!((3.8..4.2).contains(&mean), "Expected a mean of about 4: {}", mean);
+    }
+
+    #[test]
+    fn parse_fan_out_distribution() {
+        for fan_out in [
+            FanOutDistribution::Fixed,
+            FanOutDistribution::Uniform { minimum: 2, maximum: 8 },
+            FanOutDistribution::Geometric { minimum: 1, maximum: 20, mean: 4.5 }
+        ] {
+            assert_eq!(fan_out.to_string().parse::<FanOutDistribution>().unwrap(), fan_out);
+        }
+        assert!("uniform:8:2".parse::<FanOutDistribution>().is_err());
+        assert!("normal:1:2".parse::<FanOutDistribution>().is_err());
+    }
+
+    #[test]
+    fn parallel_generation_is_independent_of_number_of_threads() {
+        let json = Generator::generate_json(CHARACTERS_POLL, 16, 6, 4, 42, &GeneratorOptions::default()).unwrap();
+        assert_full_tree(&json, 0, 6, 4);
+
+        for number_of_threads in [2, 3, 8] {
+            let options = GeneratorOptions { number_of_threads, ..GeneratorOptions::default() };
+            assert_eq!(Generator::generate_json(CHARACTERS_POLL, 16, 6, 4, 42, &options).unwrap(), json);
+        }
+
+        let options = GeneratorOptions { number_of_threads: 4, object_ratio: 1.0, ..GeneratorOptions::default() };
+        assert_eq!(Generator::generate_json(CHARACTERS_POLL, 16, 0, 4, 42, &options).unwrap(), Value::Object(Map::new()));
+        for depth in 1..=2 {
+            let json = Generator::generate_json(CHARACTERS_POLL, 16, depth, 4, 42, &options).unwrap();
+            assert_full_tree(&json, 0, depth, 4);
+        }
+
+        // A ragged tree with unique keys draws a different number of values in every subtree
+        let options = GeneratorOptions {
+            fan_out: FanOutDistribution::Uniform { minimum: 1, maximum: 5 },
+            early_termination_probability: 0.2,
+            unique_keys: true,
+            ..GeneratorOptions::default()
+        };
+        let sequential_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 2, 7, 3, 7, &options).unwrap();
+        let parallel_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 2, 7, 3, 7, &GeneratorOptions { number_of_threads: 4, ..options }).unwrap();
+        assert_eq!(sequential_json, parallel_json);
+    }
+
+    #[test]
+    fn parallel_ragged_tree_shape() {
+        let options = GeneratorOptions {
+            fan_out: FanOutDistribution::Uniform { minimum: 1, maximum: 5 },
+            early_termination_probability: 0.3,
+            number_of_threads: 4,
+            ..GeneratorOptions::default()
+        };
+        let json = Generator::generate_json(CHARACTERS_POLL, 16, 6, 3, 42, &options).unwrap();
+        assert!(assert_ragged_tree(&json, 0, 6, 1, 5), "Expected a ragged tree");
+    }
+
+    #[test]
+    fn write_json_reports_summary() {
+        let mut buffer = vec!();
+        let summary = Generator::write_json(&mut buffer, CHARACTERS_POLL, 16, 3, 4, 42, &GeneratorOptions::default()).unwrap();
+        assert_eq!(summary, TreeSummary {
+            depth: 3,
+            number_of_children: 4,
+            number_of_nodes: 1 + 4 + 16 + 64,
+            number_of_leaves: 64,
+            size_in_bytes: buffer.len() as u64,
+        });
+    }
+
+    #[test]
+    fn write_json_with_target_size_reaches_target_size() {
+        for target_size in [1_000, 50_000, 1_000_000] {
+            let mut buffer = vec!();
+            let summary = Generator::write_json_with_target_size(&mut buffer, CHARACTERS_POLL, 8, 10, target_size, 42, &GeneratorOptions::default()).unwrap();
+            assert_eq!(summary.size_in_bytes, buffer.len() as u64);
+            assert!(summary.size_in_bytes >= target_size, "{:?} is smaller than {}", summary, target_size);
+            assert!(summary.size_in_bytes < target_size + 1_000, "{:?} is much bigger than {}", summary, target_size);
+            serde_json::from_slice::<Value>(&buffer).unwrap();
+        }
+    }
+
+    #[test]
+    fn write_json_with_growing_root_reaches_target_size() {
+        let options = GeneratorOptions {
+            fan_out: FanOutDistribution::Geometric { minimum: 1, maximum: 6, mean: 2.5 },
+            early_termination_probability: 0.3,
+            ..GeneratorOptions::default()
+        };
+        let target_size = 100_000;
+        let mut buffer = vec!();
+        let summary = Generator::write_json_with_growing_root(&mut buffer, CHARACTERS_POLL, 8, 4, 6, target_size, 42, &options).unwrap();
+        assert_eq!(summary.size_in_bytes, buffer.len() as u64);
+        assert!(summary.size_in_bytes >= target_size, "{:?} is smaller than {}", summary, target_size);
+        assert!(summary.size_in_bytes < target_size + 1_000, "{:?} is much bigger than {}", summary, target_size);
+
+        let json: Value = serde_json::from_slice(&buffer).unwrap();
+        let root_number_of_children = match &json {
+            Value::Array(array) => array.len(),
+            Value::Object(map) => map.len(),
+            _ => 0,
+        };
+        assert!(root_number_of_children > 6);
+
+        let options = GeneratorOptions { unique_keys: true, ..options };
+        assert!(Generator::write_json_with_growing_root(&mut io::sink(), CHARACTERS_POLL, 8, 4, 6, target_size, 42, &options).is_err());
+    }
+
+    #[test]
+    fn preset_with_needle() {
+        let needle = Needle { value: json!("needle"), is_key: false, position: NeedlePosition::Last };
+        let options = GeneratorOptions { preset: Some(Preset::EventLog), needle: Some(needle), ..GeneratorOptions::default() };
+        let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 5, 3, 4, 42, &options).unwrap();
+        assert_eq!(generated_json.json["events"].as_array().unwrap().len(), 4);
+        assert_eq!(generated_json.node_counts, NodeCounts::count(&generated_json.json));
+        assert_eq!(generated_json.json.pointer(&generated_json.needle_path.clone().unwrap()), Some(&json!("needle")));
+        // A preset is counted only after it is generated
+        let uncounted_json = Generator::generate_uncounted_json(CHARACTERS_POLL, 5, 3, 4, 42, &options).unwrap();
+        assert_eq!(uncounted_json.node_counts, None);
+        assert_eq!(uncounted_json.count(), generated_json);
+
+        let options = GeneratorOptions { needle: None, ..options };
+        let mut buffer = vec!();
+        let summary = Generator::write_json(&mut buffer, CHARACTERS_POLL, 5, 3, 4, 42, &options).unwrap();
+        assert_eq!(summary.size_in_bytes, buffer.len() as u64);
+        assert_eq!(serde_json::from_slice::<Value>(&buffer).unwrap(), Generator::generate_json(CHARACTERS_POLL, 5, 3, 4, 42, &options).unwrap());
+        assert!(Generator::write_json_with_target_size(&mut io::sink(), CHARACTERS_POLL, 5, 4, 1_000, 42, &options).is_err());
+    }
+
+    #[test]
+    fn write_json_same_seed_writes_same_tree() {
+        let mut first = vec!();
+        Generator::write_json(&mut first, CHARACTERS_POLL, 5, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        let mut second = vec!();
+        Generator::write_json(&mut second, CHARACTERS_POLL, 5, 4, 3, 42, &GeneratorOptions::default()).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn unique_keys_keep_every_child() {
+        let options = GeneratorOptions { object_ratio: 1.0, unique_keys: true, ..GeneratorOptions::default() };
+        let json = Generator::generate_json("ab", 1, 3, 2, 42, &options).unwrap();
+        assert_full_tree(&json, 0, 3, 2);
+
+        let mut buffer = vec!();
+        Generator::write_json(&mut buffer, "ab", 1, 3, 2, 42, &options).unwrap();
+        assert_full_tree(&serde_json::from_slice(&buffer).unwrap(), 0, 3, 2);
+
+        let options = GeneratorOptions { number_of_threads: 2, ..options };
+        assert_full_tree(&Generator::generate_json("ab", 1, 10, 2, 42, &options).unwrap(), 0, 10, 2);
+
+        assert!(Generator::generate_json("aa", 1, 3, 2, 42, &options).is_err());
+    }
+
+    #[test]
+    fn node_counts_match_generated_tree() {
+        let repeated_names_options = GeneratorOptions { object_ratio: 1.0, ..GeneratorOptions::default() };
+        let ragged_options = GeneratorOptions {
+            fan_out: FanOutDistribution::Uniform { minimum: 0, maximum: 5 },
+            early_termination_probability: 0.2,
+            ..GeneratorOptions::default()
+        };
+        for options in [repeated_names_options, ragged_options] {
+            for number_of_threads in [1, 4] {
+                let options = GeneratorOptions { number_of_threads, ..options.clone() };
+                let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 1, 6, 4, 42, &options).unwrap();
+                assert_eq!(generated_json.node_counts, NodeCounts::count(&generated_json.json));
+            }
         }
+
+        let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 1, 3, 4, 42, &GeneratorOptions::default()).unwrap();
+        assert!(generated_json.node_counts.number_of_nodes < 1 + 4 + 16 + 64, "Expected repeated names in {}", generated_json.json);
     }
 
-    fn add_leaf_children_to_map(&self, object: &mut Map<String, Value>) {
-        for _node_count in 0..self.number_of_children {
-            let child_node_name = self.get_random_node_name();
-            let child_node = json_type::get_random_leaf_json();
-            object.insert(child_node_name, child_node);
+    #[test]
+    fn manifest_size_and_verification() {
+        let raw_json = br#"{"a":[1,2,3]}"#;
+        let checksum = Checksum::from_bytes(raw_json);
+        let mut manifest = Manifest {
+            file_name: String::from("generatedJson.json"),
+            seed: 42,
+            number_of_letters: 1,
+            characters_poll: String::from(CHARACTER_POLL),
+            depth: 2,
+            number_of_children: 3,
+            target_size: None,
+            size_in_bytes: checksum.get_size_in_bytes(),
+            number_of_nodes: 5,
+            number_of_leaves: 3,
+            checksum: checksum.get_checksum(),
+            escape_unicode: false,
+            needle_path: None,
+            number_of_records: None,
+            schema_path: None,
+            schema_checksum: None,
+            sample_path: None,
+            generator_options: GeneratorOptions::default(),
+        };
+        assert!(manifest.verify(raw_json).is_ok());
+        assert!(manifest.verify(br#"{"a":[1,2,4]}"#).is_err());
+        assert_eq!(manifest.get_size(), "13B");
+        assert_eq!(manifest.get_path_to_json(Path::new("/tmp/jsons/generatedJson.json.manifest.json")), Path::new("/tmp/jsons/generatedJson.json"));
+
+        manifest.size_in_bytes = 3 << 19;
+        assert_eq!(manifest.get_size(), "1.5MB");
+        let parsed_manifest: Manifest = serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(parsed_manifest, manifest);
+    }
+
+    #[test]
+    fn write_json_output_formats() {
+        let mut compact_buffer = vec!();
+        Generator::write_json(&mut compact_buffer, CHARACTERS_POLL, 5, 3, 3, 42, &GeneratorOptions::default()).unwrap();
+        for output_format in ["pretty:4", "pretty:1:tabs", "oneKeyPerLine"] {
+            let options = GeneratorOptions { output_format: output_format.parse().unwrap(), ..GeneratorOptions::default() };
+            let mut buffer = vec!();
+            let summary = Generator::write_json(&mut buffer, CHARACTERS_POLL, 5, 3, 3, 42, &options).unwrap();
+            assert_eq!(summary.size_in_bytes, buffer.len() as u64);
+            assert!(buffer.contains(&b'\n'), "The {} output has no new lines", output_format);
+            let json: Value = serde_json::from_slice(&buffer).unwrap();
+            assert_eq!(json, serde_json::from_slice::<Value>(&compact_buffer).unwrap());
+
+            // The default leaves and names have no whitespace
+            buffer.retain(|byte| !b" \t\n".contains(byte));
+            assert_eq!(buffer, compact_buffer, "The {} output has different tokens", output_format);
         }
+
+        let options = GeneratorOptions { output_format: OutputFormat::OneKeyPerLine, ..GeneratorOptions::default() };
+        assert!(Generator::write_json_lines(&mut vec!(), CHARACTERS_POLL, 5, 3, 3, 2, 42, &options).is_err());
     }
 
-    fn add_leaf_children<'a, I>(&self, iterator: I) -> Result<(), Box<dyn Error + Send + Sync>>
-    where I: Iterator<Item = &'a mut Value> {
-        for next_level_node in iterator {
-            match next_level_node {
-                Value::Array(array) => self.add_leaf_children_to_array(array),
-                Value::Object(map) => self.add_leaf_children_to_map(map),
-                _ => return Err(Box::from(format!("Invalid child node type: {}", next_level_node)))
+    #[test]
+    fn json_lines_records() {
+        let records = Generator::generate_json_lines(CHARACTERS_POLL, 5, 3, 3, 4, 42, &GeneratorOptions::default()).unwrap();
+        assert_eq!(records.len(), 4);
+        for (record_index, record) in records.iter().enumerate() {
+            let seed = get_record_seed(42, record_index as u64);
+            assert_eq!(record.json, Generator::generate_json(CHARACTERS_POLL, 5, 3, 3, seed, &GeneratorOptions::default()).unwrap());
+        }
+        assert_ne!(records[0].json, records[1].json);
+        // The next seed doesn't generate the same records shifted by one
+        let next_seed_records = Generator::generate_json_lines(CHARACTERS_POLL, 5, 3, 3, 4, 43, &GeneratorOptions::default()).unwrap();
+        assert!(next_seed_records.iter().all(|next_seed_record| records.iter().all(|record| record.json != next_seed_record.json)));
+
+        let mut buffer = vec!();
+        let summary = Generator::write_json_lines(&mut buffer, CHARACTERS_POLL, 5, 3, 3, 4, 42, &GeneratorOptions::default()).unwrap();
+        assert_eq!(summary.size_in_bytes, buffer.len() as u64);
+        let lines: Vec<&[u8]> = buffer.split(|byte| *byte == b'\n').collect();
+        assert_eq!(lines.len(), 5, "Every record should end with a new line");
+        assert!(lines[4].is_empty());
+        for line in &lines[..4] {
+            assert_full_tree(&serde_json::from_slice(line).unwrap(), 0, 3, 3);
+        }
+        assert_eq!(summary.number_of_nodes, 4 * (1 + 3 + 9 + 27));
+    }
+
+    #[test]
+    fn plant_needle_at_position() {
+        let mut leaves = vec!();
+        let json = Generator::generate_json(CHARACTERS_POLL, 5, 3, 3, 42, &GeneratorOptions::default()).unwrap();
+        visit_in_document_order(&json, |path, node| {
+            if !node.is_array() && !node.is_object() {
+                leaves.push(json_pointer::to_json_pointer(path));
             }
+            true
+        });
+
+        let needle_value = json!("needle");
+        for (position, expected_path) in [
+            (NeedlePosition::First, &leaves[0]),
+            (NeedlePosition::Middle, &leaves[leaves.len() / 2]),
+            (NeedlePosition::Last, &leaves[leaves.len() - 1]),
+        ] {
+            let needle = Needle { value: needle_value.clone(), is_key: false, position };
+            let options = GeneratorOptions { needle: Some(needle), ..GeneratorOptions::default() };
+            let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 5, 3, 3, 42, &options).unwrap();
+            let needle_path = generated_json.needle_path.unwrap();
+            assert_eq!(&needle_path, expected_path);
+            assert_eq!(generated_json.json.pointer(&needle_path), Some(&needle_value));
         }
 
-        Ok(())
+        let needle = Needle { value: needle_value.clone(), is_key: false, position: NeedlePosition::Depth { depth: 2 } };
+        let options = GeneratorOptions { needle: Some(needle), early_termination_probability: 0.5, ..GeneratorOptions::default() };
+        let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 5, 3, 3, 42, &options).unwrap();
+        let needle_path = generated_json.needle_path.unwrap();
+        assert_eq!(needle_path.matches('/').count(), 2);
+        assert_eq!(generated_json.json.pointer(&needle_path), Some(&needle_value));
     }
-    /* #endregion */
-}
\ No newline at end of file
+
+    #[test]
+    fn plant_needle_key() {
+        let needle = Needle { value: json!("needle/key"), is_key: true, position: NeedlePosition::Middle };
+        let options = GeneratorOptions { object_ratio: 1.0, needle: Some(needle), ..GeneratorOptions::default() };
+        let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 5, 3, 3, 42, &options).unwrap();
+        let needle_path = generated_json.needle_path.unwrap();
+        assert!(needle_path.ends_with("/needle~1key"), "Unexpected needle path {}", needle_path);
+        assert!(generated_json.json.pointer(&needle_path).is_some());
+        assert_eq!(generated_json.node_counts, NodeCounts::count(&generated_json.json));
+    }
+
+    #[test]
+    fn invalid_needle() {
+        let container_needle = Needle { value: json!([1]), is_key: false, position: NeedlePosition::Last };
+        let number_key_needle = Needle { value: json!(1), is_key: true, position: NeedlePosition::Last };
+        for needle in [container_needle, number_key_needle] {
+            let options = GeneratorOptions { needle: Some(needle), ..GeneratorOptions::default() };
+            assert!(Generator::generate_json(CHARACTERS_POLL, 5, 3, 3, 42, &options).is_err());
+        }
+
+        // Every key of a single letter pool is the same letter, so renaming a member to it would overwrite its sibling
+        let existing_key_needle = Needle { value: json!("a"), is_key: true, position: NeedlePosition::Middle };
+        let options = GeneratorOptions { object_ratio: 1.0, needle: Some(existing_key_needle), ..GeneratorOptions::default() };
+        assert!(Generator::generate_json("a", 1, 3, 3, 42, &options).is_err());
+
+        // The default leaves are often null
+        let generated_needle = Needle { value: Value::Null, is_key: false, position: NeedlePosition::Last };
+        let options = GeneratorOptions { needle: Some(generated_needle), ..GeneratorOptions::default() };
+        let error = Generator::generate_json(CHARACTERS_POLL, 5, 3, 3, 42, &options).unwrap_err();
+        assert!(error.to_string().contains("times"), "Unexpected error: {}", error);
+
+        let too_deep_needle = Needle { value: json!("needle"), is_key: false, position: NeedlePosition::Depth { depth: 10 } };
+        let options = GeneratorOptions { needle: Some(too_deep_needle), ..GeneratorOptions::default() };
+        assert!(Generator::generate_json(CHARACTERS_POLL, 5, 3, 3, 42, &options).is_err());
+        assert!(Generator::write_json(&mut vec!(), CHARACTERS_POLL, 5, 3, 3, 42, &options).is_err());
+    }
+
+    /// Returns the depth of the chain, every container has the given number of children and only its last one can be a container.
+    fn assert_deep_chain(root: &Value, number_of_children: usize) -> u32 {
+        let mut depth = 0;
+        let mut current_node = Some(root);
+        while let Some(node) = current_node.take() {
+            let children: Vec<&Value> = match node {
+                Value::Array(array) => array.iter().collect(),
+                Value::Object(map) => map.values().collect(),
+                _ => break,
+            };
+            depth += 1;
+            assert_eq!(children.len(), number_of_children);
+            let containers: Vec<&Value> = children.into_iter().filter(|child| child.is_array() || child.is_object()).collect();
+            assert!(containers.len() <= 1, "Found {} containers at level {} of a deep chain", containers.len(), depth);
+            current_node = containers.first().copied();
+        }
+        depth
+    }
+
+    #[test]
+    fn deep_chain_shape() {
+        // Dropping and parsing the chain are recursive
+        thread::Builder::new()
+            .stack_size(get_stack_size(3_000))
+            .spawn(|| {
+                let options = GeneratorOptions { shape: TreeShape::DeepChain, ..GeneratorOptions::default() };
+                let generated_json = Generator::generate_json_with_summary(CHARACTERS_POLL, 8, 3_000, 3, 42, &options).unwrap();
+                assert_eq!(assert_deep_chain(&generated_json.json, 3), 3_000);
+                assert_eq!(generated_json.node_counts, NodeCounts { number_of_nodes: 1 + 3 * 3_000, number_of_leaves: 2 * 2_999 + 3 });
+
+                let options = GeneratorOptions { number_of_threads: 4, ..options };
+                assert_eq!(assert_deep_chain(&Generator::generate_json(CHARACTERS_POLL, 8, 3_000, 3, 42, &options).unwrap(), 3), 3_000);
+
+                let mut buffer = vec!();
+                let summary = Generator::write_json(&mut buffer, CHARACTERS_POLL, 8, 3_000, 3, 42, &options).unwrap();
+                assert_eq!(summary.number_of_nodes, 1 + 3 * 3_000);
+                let mut deserializer = serde_json::Deserializer::from_slice(&buffer);
+                deserializer.disable_recursion_limit();
+                assert_eq!(assert_deep_chain(&Value::deserialize(&mut deserializer).unwrap(), 3), 3_000);
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
@@ -1,16 +1,29 @@
 // #![allow(unused, dead_code)]
 
 pub mod json_generator;
+pub mod presets;
+pub mod schema_generator;
+pub mod shape_statistics;
+pub mod sweep;
 
 pub mod utils {
     pub mod json_type;
     pub mod randomizer;
     pub mod math_data_collector;
+    pub mod unicode_escaping_writer;
+    pub mod checksum;
+    pub mod json_pointer;
+    pub mod json_lines;
+    pub mod output_format;
 }
 
 pub mod search_tree {
     pub mod breadth_first_search;
     pub mod depth_first_search;
+    pub mod iterative_depth_first_search;
+    pub mod json_path;
+    pub mod query;
+    pub mod search_match;
 }
 
 pub mod test_json {
@@ -3,10 +3,10 @@
 // Standard
 use std::{
     env,
-    fs,
     error::Error,
     path::{Path, PathBuf},
     sync::Arc,
+    thread,
 };
 
 // 3rd Party
@@ -17,7 +17,7 @@ use serde_json::json;
 use tokio::{runtime::Builder, task};
 
 // Project
-use rust_multi_json_benchmark::{test_json::{reporter::{REPORT_INSTANCE, ReportData}, run_test_loop::RunTestLoop, measurement::Measurement}};
+use rust_multi_json_benchmark::{json_generator, test_json::{reporter::{REPORT_INSTANCE, ReportData}, run_test_loop::{RunTestLoop, SearchTarget}, measurement::Measurement}};
 use rust_multi_json_benchmark::test_json::{
     config::Configs,
     excel_generator::ExcelGenerator
@@ -53,8 +53,7 @@ static DEFAULT_PATH_TO_DEBUG_DIRECTORY: Lazy<String> = Lazy::new(|| {
 
 /* #region CLI Arguments */
 fn parse_config(source: &str) -> Result<Configs, Box<dyn Error>> {
-    let raw_config_file = fs::read_to_string(Path::new(source))?;
-    let configs: Configs = serde_json::from_str(&raw_config_file)?;
+    let configs = Configs::read(Path::new(source)).map_err(|error| error as Box<dyn Error>)?;
     Ok(configs)
 }
 
@@ -110,20 +109,32 @@ struct OptionalArguments {
 
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let options = OptionalArguments::from_args();
-    let runtime = if options.single_thread {
-        Builder::new_current_thread()
-            .enable_all()
-            .build()
-    } else {
-        let mut runtime_builder = Builder::new_multi_thread();
-        runtime_builder.enable_all();
-        if let Some(thread_count) = options.thread_count {
-            runtime_builder.worker_threads(thread_count);
-        }
-        runtime_builder.build()
-    }.expect("Failed building the Runtime");
-
-    runtime.block_on(async { async_main(options).await })
+    let deepest_depth = options.configs.iter().map(|config| config.depth).max().unwrap_or(0);
+    let stack_size = json_generator::get_stack_size(deepest_depth);
+
+    // The single thread runtime runs on the current thread, so it needs a thread with the same stack size too
+    thread::Builder::new()
+        .name(String::from("json_tester"))
+        .stack_size(stack_size)
+        .spawn(move || {
+            let runtime = if options.single_thread {
+                Builder::new_current_thread()
+                    .enable_all()
+                    .thread_stack_size(stack_size)
+                    .build()
+            } else {
+                let mut runtime_builder = Builder::new_multi_thread();
+                runtime_builder.enable_all().thread_stack_size(stack_size);
+                if let Some(thread_count) = options.thread_count {
+                    runtime_builder.worker_threads(thread_count);
+                }
+                runtime_builder.build()
+            }.expect("Failed building the Runtime");
+
+            runtime.block_on(async { async_main(options).await })
+        })?
+        .join()
+        .map_err(|_error| "The test thread panicked")?
 }
 
 async fn async_main(mut options: OptionalArguments) -> Result<(), Box<dyn Error + Send + Sync>> {    
@@ -135,13 +146,13 @@ async fn async_main(mut options: OptionalArguments) -> Result<(), Box<dyn Error
     let mut test_names = Vec::with_capacity(options.configs.len());
 
     for config in options.configs.iter_mut() {
-        config.raw = Some(Arc::new(fs::read_to_string(&config.path)?));
+        config.load_raw_json()?;
         test_names.push(Arc::clone(&config.name));
     }
 
     let value_to_search: i64 = 2_000_000_000;
     let value_to_search = json!(value_to_search);
-    let test_runner = Arc::new(RunTestLoop::new(options.test_counter, value_to_search));
+    let test_runner = Arc::new(RunTestLoop::new(options.test_counter));
     let mut task_handlers = Vec::with_capacity(options.configs.len());
     /* #endregion */
 
@@ -153,9 +164,23 @@ async fn async_main(mut options: OptionalArguments) -> Result<(), Box<dyn Error
         let number_of_letters = config.number_of_letters;
         let depth = config.depth;
         let number_of_children = config.number_of_children;
+        let number_of_records = config.number_of_records;
+        let schema = config.schema.clone();
+        let seed = config.seed;
+        let generator_options = Arc::clone(&config.generator_options);
         let raw_json = Arc::clone(config.raw.as_ref().expect("Config doesn't contain raw of the JSON file"));
+        // A planted needle must be found, otherwise the search scans the whole tree for a value that isn't in it
+        let search_target = Arc::new(match &config.generator_options.needle {
+            Some(needle) => SearchTarget::planted(
+                needle.value.clone(),
+                config.manifest.as_ref().and_then(|manifest| manifest.needle_path.clone())
+            ),
+            None => SearchTarget::absent(value_to_search.clone()),
+        });
+        let json_paths = Arc::clone(&config.json_paths);
+        let find_all_value = Arc::clone(&config.find_all_value);
         task_handlers.push(task::spawn(async move {
-            test_runner.run_test(json_name, number_of_letters, depth, number_of_children, raw_json).await
+            test_runner.run_test(json_name, number_of_letters, depth, number_of_children, number_of_records, schema, seed, generator_options, raw_json, search_target, json_paths, find_all_value).await
         }));
     }
     for join_handler in task_handlers {
@@ -187,7 +212,7 @@ async fn async_main(mut options: OptionalArguments) -> Result<(), Box<dyn Error
             let test_case = report
                 .get(&test_name)
                 .ok_or_else(|| format!("Report doesn't contain the test name: {}", test_name))?;
-            excel_generator.append_worksheet(&test_name, test_case)?;
+            excel_generator.append_worksheet(&test_name, test_case, reporter.get_workloads().get(&test_name))?;
         }
     }
 
@@ -0,0 +1,301 @@
+/* #region Imports */
+// 3rd Party
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use strum_macros::{Display, EnumString};
+
+// Project
+use crate::utils::randomizer;
+/* #endregion */
+
+/* #region Vocabulary */
+const WORDS: [&str; 32] = [
+    "alpha", "bravo", "cache", "delta", "engine", "fast", "graph", "harbor", "index", "json", "kernel", "latency", "merge", "node",
+    "offset", "parser", "query", "record", "stream", "token", "update", "value", "worker", "yield", "zone", "batch", "cluster",
+    "deploy", "event", "filter", "gateway", "handler",
+];
+const FIRST_NAMES: [&str; 16] = [
+    "Avery", "Blake", "Casey", "Dana", "Eden", "Finley", "Gray", "Harper", "Indigo", "Jordan", "Kai", "Logan", "Morgan", "Noa", "Quinn", "Riley",
+];
+const LAST_NAMES: [&str; 12] = ["Adler", "Brooks", "Cohen", "Diaz", "Evans", "Fischer", "Garcia", "Hayes", "Ito", "Levi", "Novak", "Silva"];
+const DEPARTMENTS: [&str; 6] = ["engineering", "sales", "support", "finance", "research", "operations"];
+const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+const SERVICES: [&str; 6] = ["auth", "billing", "catalog", "checkout", "search", "shipping"];
+const HTTP_METHODS: [&str; 4] = ["GET", "POST", "PUT", "DELETE"];
+const HTTP_STATUSES: [u16; 6] = [200, 201, 204, 400, 404, 500];
+const HASHTAGS: [&str; 10] = ["rust", "json", "benchmark", "travel", "food", "music", "news", "sports", "art", "science"];
+/* #endregion */
+
+/* #region Shape Limits */
+const GEO_JSON_MAXIMAL_LINE_LENGTH: usize = 8;
+const GEO_JSON_MAXIMAL_RING_LENGTH: usize = 8;
+/// Nested sections below the top level sections of a config.
+const NESTED_CONFIG_MAXIMAL_DEPTH: u32 = 4;
+const NESTED_CONFIG_MAXIMAL_SUBSECTIONS: u32 = 3;
+const SOCIAL_FEED_MAXIMAL_COMMENTS: usize = 5;
+const SOCIAL_FEED_MAXIMAL_REPLIES: usize = 3;
+/* #endregion */
+
+/// A document shaped like a real-world payload, the number of items is its number of rows, features, top level sections, events or posts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum Preset {
+    /// An array of flat records that all have the same fields, like the rows of a table.
+    Table,
+    /// A GeoJSON FeatureCollection of points, line strings and polygons with their properties.
+    GeoJson,
+    /// Sections of settings with nested subsections, like the config of a service.
+    NestedConfig,
+    /// Log events with a request context.
+    EventLog,
+    /// Posts with their authors, tags, comments and replies.
+    SocialFeed,
+}
+
+impl Preset {
+    /// The deepest node of the preset, the root is at depth 0.
+    pub fn get_depth(&self) -> u32 {
+        match self {
+            // [{"field": leaf}]
+            Preset::Table => 2,
+            // {"features": [{"geometry": {"coordinates": [[[longitude, latitude]]]}}]}
+            Preset::GeoJson => 7,
+            // {"section": {"subsection"...: {"ports": [port]}}}
+            Preset::NestedConfig => NESTED_CONFIG_MAXIMAL_DEPTH + 3,
+            // {"events": [{"context": {"field": leaf}}]}
+            Preset::EventLog => 4,
+            // {"posts": [{"comments": [{"replies": [{"author": {"field": leaf}}]}]}]}
+            Preset::SocialFeed => 8,
+        }
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, random_generator: &mut R, number_of_items: u32) -> Value {
+        match self {
+            Preset::Table => Value::Array((0..number_of_items).map(|index| get_table_row(random_generator, index)).collect()),
+            Preset::GeoJson => json!({
+                "type": "FeatureCollection",
+                "features": (0..number_of_items).map(|index| get_geo_json_feature(random_generator, index)).collect::<Vec<Value>>(),
+            }),
+            Preset::NestedConfig => {
+                let mut config = Map::new();
+                config.insert(String::from("version"), json!(format!("{}.{}.{}", random_generator.gen_range(0..5), random_generator.gen_range(0..20), random_generator.gen_range(0..100))));
+                for index in 0..number_of_items {
+                    config.insert(format!("{}{}", get_word(random_generator), index), get_config_section(random_generator, NESTED_CONFIG_MAXIMAL_DEPTH));
+                }
+                Value::Object(config)
+            },
+            Preset::EventLog => json!({
+                "events": (0..number_of_items).map(|index| get_log_event(random_generator, index)).collect::<Vec<Value>>(),
+            }),
+            Preset::SocialFeed => json!({
+                "posts": (0..number_of_items).map(|index| get_post(random_generator, index)).collect::<Vec<Value>>(),
+                "nextCursor": format!("{:016x}", random_generator.gen::<u64>()),
+            }),
+        }
+    }
+}
+
+/* #region Leaves */
+fn get_word<R: Rng + ?Sized>(random_generator: &mut R) -> &'static str {
+    randomizer::get_random_value_from_array::<&str, _>(random_generator, &WORDS)
+}
+
+fn get_sentence<R: Rng + ?Sized>(random_generator: &mut R, maximal_number_of_words: usize) -> String {
+    let number_of_words = random_generator.gen_range(1..=maximal_number_of_words);
+    (0..number_of_words).map(|_index| get_word(random_generator)).collect::<Vec<_>>().join(" ")
+}
+
+fn get_full_name<R: Rng + ?Sized>(random_generator: &mut R) -> String {
+    let first_name = randomizer::get_random_value_from_array(random_generator, &FIRST_NAMES);
+    let last_name = randomizer::get_random_value_from_array(random_generator, &LAST_NAMES);
+    format!("{} {}", first_name, last_name)
+}
+
+fn get_timestamp<R: Rng + ?Sized>(random_generator: &mut R) -> String {
+    format!(
+        "2024-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        random_generator.gen_range(1..=12), random_generator.gen_range(1..=28),
+        random_generator.gen_range(0..24), random_generator.gen_range(0..60), random_generator.gen_range(0..60), random_generator.gen_range(0..1000),
+    )
+}
+
+/// Rounded to 6 decimal places, like most GeoJSON producers do.
+fn get_position<R: Rng + ?Sized>(random_generator: &mut R) -> Value {
+    let longitude: f64 = random_generator.gen_range(-180.0..180.0);
+    let latitude: f64 = random_generator.gen_range(-90.0..90.0);
+    json!([(longitude * 1e6).round() / 1e6, (latitude * 1e6).round() / 1e6])
+}
+/* #endregion */
+
+/* #region Items */
+fn get_table_row<R: Rng + ?Sized>(random_generator: &mut R, index: u32) -> Value {
+    let name = get_full_name(random_generator);
+    let email = format!("{}{}@example.com", name.to_lowercase().replace(' ', "."), index);
+    json!({
+        "id": index,
+        "name": name,
+        "email": email,
+        "age": random_generator.gen_range(18..80),
+        "department": randomizer::get_random_value_from_array(random_generator, &DEPARTMENTS),
+        "salary": (random_generator.gen_range(30_000.0..250_000.0_f64) * 100.0).round() / 100.0,
+        "active": random_generator.gen_bool(0.8),
+        "manager": if random_generator.gen_bool(0.3) { Value::Null } else { json!(random_generator.gen_range(0..index.max(1))) },
+        "createdAt": get_timestamp(random_generator),
+    })
+}
+
+fn get_geo_json_feature<R: Rng + ?Sized>(random_generator: &mut R, index: u32) -> Value {
+    let geometry = match random_generator.gen_range(0..3) {
+        0 => json!({"type": "Point", "coordinates": get_position(random_generator)}),
+        1 => {
+            let line_length = random_generator.gen_range(2..=GEO_JSON_MAXIMAL_LINE_LENGTH);
+            json!({"type": "LineString", "coordinates": (0..line_length).map(|_index| get_position(random_generator)).collect::<Vec<Value>>()})
+        },
+        _ => {
+            // A closed linear ring, the last position is the first one
+            let ring_length = random_generator.gen_range(3..=GEO_JSON_MAXIMAL_RING_LENGTH);
+            let mut ring: Vec<Value> = (0..ring_length).map(|_index| get_position(random_generator)).collect();
+            ring.push(ring[0].clone());
+            json!({"type": "Polygon", "coordinates": [ring]})
+        },
+    };
+    json!({
+        "type": "Feature",
+        "id": index,
+        "geometry": geometry,
+        "properties": {
+            "name": get_sentence(random_generator, 3),
+            "population": random_generator.gen_range(0..10_000_000),
+            "elevation": (random_generator.gen_range(-400.0..8_848.0_f64) * 10.0).round() / 10.0,
+            "capital": random_generator.gen_bool(0.1),
+        },
+    })
+}
+
+/// A section at the given remaining depth has up to that many levels of subsections below it.
+fn get_config_section<R: Rng + ?Sized>(random_generator: &mut R, remaining_depth: u32) -> Value {
+    let number_of_ports = random_generator.gen_range(0..4);
+    let mut section = Map::new();
+    section.insert(String::from("enabled"), json!(random_generator.gen_bool(0.7)));
+    section.insert(String::from("host"), json!(format!("{}.{}.internal", get_word(random_generator), get_word(random_generator))));
+    section.insert(String::from("ports"), json!((0..number_of_ports).map(|_index| random_generator.gen_range(1024..65536)).collect::<Vec<u32>>()));
+    section.insert(String::from("timeoutMs"), json!(random_generator.gen_range(1..300) * 100));
+    section.insert(String::from("retries"), json!(random_generator.gen_range(0..5)));
+    section.insert(String::from("description"), if random_generator.gen_bool(0.5) { json!(get_sentence(random_generator, 8)) } else { Value::Null });
+
+    if remaining_depth > 0 {
+        let number_of_subsections = random_generator.gen_range(0..=NESTED_CONFIG_MAXIMAL_SUBSECTIONS);
+        for index in 0..number_of_subsections {
+            section.insert(format!("{}{}", get_word(random_generator), index), get_config_section(random_generator, remaining_depth - 1));
+        }
+    }
+    Value::Object(section)
+}
+
+fn get_log_event<R: Rng + ?Sized>(random_generator: &mut R, index: u32) -> Value {
+    let status = *randomizer::get_random_value_from_array(random_generator, &HTTP_STATUSES);
+    let level = match status {
+        500 => "error",
+        400 | 404 => "warn",
+        _ => randomizer::get_random_value_from_array(random_generator, &LOG_LEVELS[..3]),
+    };
+    json!({
+        "sequence": index,
+        "timestamp": get_timestamp(random_generator),
+        "level": level,
+        "service": randomizer::get_random_value_from_array(random_generator, &SERVICES),
+        "message": get_sentence(random_generator, 10),
+        "requestId": format!("{:032x}", random_generator.gen::<u128>()),
+        "durationMs": (random_generator.gen_range(0.1..2_000.0_f64) * 1_000.0).round() / 1_000.0,
+        "context": {
+            "method": randomizer::get_random_value_from_array(random_generator, &HTTP_METHODS),
+            "path": format!("/api/v{}/{}/{}", random_generator.gen_range(1..4), get_word(random_generator), random_generator.gen_range(0..100_000)),
+            "status": status,
+            "userId": if random_generator.gen_bool(0.2) { Value::Null } else { json!(random_generator.gen_range(0..1_000_000)) },
+        },
+    })
+}
+
+fn get_author<R: Rng + ?Sized>(random_generator: &mut R) -> Value {
+    let name = get_full_name(random_generator);
+    json!({
+        "id": random_generator.gen_range(0..1_000_000),
+        "handle": format!("@{}", name.to_lowercase().replace(' ', "_")),
+        "name": name,
+        "verified": random_generator.gen_bool(0.1),
+    })
+}
+
+fn get_comment<R: Rng + ?Sized>(random_generator: &mut R, maximal_number_of_replies: usize) -> Value {
+    let mut comment = json!({
+        "id": format!("{:012x}", random_generator.gen::<u64>() >> 16),
+        "author": get_author(random_generator),
+        "text": get_sentence(random_generator, 20),
+        "likes": random_generator.gen_range(0..500),
+        "createdAt": get_timestamp(random_generator),
+    });
+    if maximal_number_of_replies > 0 {
+        let number_of_replies = random_generator.gen_range(0..=maximal_number_of_replies);
+        comment["replies"] = Value::Array((0..number_of_replies).map(|_index| get_comment(random_generator, 0)).collect());
+    }
+    comment
+}
+
+fn get_post<R: Rng + ?Sized>(random_generator: &mut R, index: u32) -> Value {
+    let number_of_tags = random_generator.gen_range(0..4);
+    let number_of_comments = random_generator.gen_range(0..=SOCIAL_FEED_MAXIMAL_COMMENTS);
+    json!({
+        "id": index,
+        "author": get_author(random_generator),
+        "text": get_sentence(random_generator, 40),
+        "createdAt": get_timestamp(random_generator),
+        "likes": random_generator.gen_range(0..100_000),
+        "shares": random_generator.gen_range(0..10_000),
+        "tags": (0..number_of_tags).map(|_index| *randomizer::get_random_value_from_array(random_generator, &HASHTAGS)).collect::<Vec<&str>>(),
+        "comments": (0..number_of_comments).map(|_index| get_comment(random_generator, SOCIAL_FEED_MAXIMAL_REPLIES)).collect::<Vec<Value>>(),
+    })
+}
+/* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // Standard
+    use std::str::FromStr;
+
+    // Project
+    use super::*;
+    use crate::shape_statistics::ShapeStatistics;
+    /* #endregion */
+
+    const PRESETS: [Preset; 5] = [Preset::Table, Preset::GeoJson, Preset::NestedConfig, Preset::EventLog, Preset::SocialFeed];
+
+    #[test]
+    fn presets_stay_within_their_depth() {
+        for preset in PRESETS {
+            for seed in 0..20 {
+                let json = preset.generate(&mut randomizer::get_seeded_random_generator(seed), 10);
+                let depth = ShapeStatistics::new(&json).get_depth();
+                assert!(depth <= preset.get_depth(), "{} generated a JSON of depth {}", preset, depth);
+            }
+            assert_eq!(Preset::from_str(&preset.to_string()).unwrap(), preset);
+        }
+    }
+
+    #[test]
+    fn presets_have_the_number_of_items() {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        assert_eq!(Preset::Table.generate(&mut random_generator, 7).as_array().unwrap().len(), 7);
+        assert_eq!(Preset::GeoJson.generate(&mut random_generator, 7)["features"].as_array().unwrap().len(), 7);
+        assert_eq!(Preset::EventLog.generate(&mut random_generator, 7)["events"].as_array().unwrap().len(), 7);
+        assert_eq!(Preset::SocialFeed.generate(&mut random_generator, 7)["posts"].as_array().unwrap().len(), 7);
+        // The version and the sections
+        assert_eq!(Preset::NestedConfig.generate(&mut random_generator, 7).as_object().unwrap().len(), 8);
+
+        let rows = Preset::Table.generate(&mut random_generator, 3);
+        let keys: Vec<Vec<&String>> = rows.as_array().unwrap().iter().map(|row| row.as_object().unwrap().keys().collect()).collect();
+        assert!(keys.iter().all(|row_keys| *row_keys == keys[0]), "The rows have different fields");
+    }
+}
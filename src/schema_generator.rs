@@ -0,0 +1,357 @@
+/* #region Imports */
+// Standard
+use std::{error::Error, fs, path::Path};
+
+// 3rd Party
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+// Project
+use crate::{
+    json_generator::{self, GeneratedJson, UncountedJson},
+    utils::{checksum::Checksum, json_type::{self, LeafOptions, LeafTypeWeights}, randomizer},
+};
+/* #endregion */
+
+/// Without maxItems, an array has up to this many items more than its minItems.
+const DEFAULT_ADDITIONAL_ITEMS: usize = 5;
+/// The chance of a property that isn't required being generated.
+const OPTIONAL_PROPERTY_PROBABILITY: f64 = 0.5;
+/// The keywords that the generated documents conform to, and the annotations that don't change which documents conform.
+/// Any other keyword (such as pattern, multipleOf or $ref) is rejected, since the documents might not conform to it.
+/// Only the declared properties are generated, so every document conforms to additionalProperties.
+const SUPPORTED_KEYWORDS: [&str; 22] = [
+    "type", "enum", "const", "properties", "required", "additionalProperties", "items", "minItems", "maxItems",
+    "minLength", "maxLength", "minimum", "maximum",
+    "$schema", "$id", "$comment", "title", "description", "default", "examples", "deprecated", "readOnly",
+];
+
+/* #region Schema */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+#[derive(Debug)]
+struct Property {
+    name: String,
+    schema: SchemaNode,
+    is_required: bool,
+}
+
+/// A schema after its keywords were checked, only the keywords of its types are kept.
+#[derive(Debug)]
+struct SchemaNode {
+    /// From enum or const, they replace the types.
+    values: Option<Vec<Value>>,
+    types: Vec<SchemaType>,
+    properties: Vec<Property>,
+    items: Option<Box<SchemaNode>>,
+    minimal_items: usize,
+    maximal_items: usize,
+    /// The string and number bounds, as the options of a string or a number leaf.
+    leaf_options: LeafOptions,
+}
+
+fn get_usize_keyword(schema: &Map<String, Value>, keyword: &str) -> Result<Option<usize>, String> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|value| usize::try_from(value).ok())
+            .map(Some)
+            .ok_or_else(|| format!("{} has to be a none negative integer: {}", keyword, value)),
+    }
+}
+
+fn get_f64_keyword(schema: &Map<String, Value>, keyword: &str) -> Result<Option<f64>, String> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value.as_f64().map(Some).ok_or_else(|| format!("{} has to be a number: {}", keyword, value)),
+    }
+}
+
+impl SchemaNode {
+    /// The path is the JSON Pointer of the schema, for the error messages.
+    fn parse(schema: &Value, path: &str) -> Result<SchemaNode, String> {
+        let schema = schema.as_object().ok_or_else(|| format!("The schema at '{}' has to be an object", path))?;
+        if let Some(keyword) = schema.keys().find(|keyword| !SUPPORTED_KEYWORDS.contains(&keyword.as_str())) {
+            return Err(format!("The schema at '{}' uses the unsupported keyword {}", path, keyword));
+        }
+
+        let values = match (schema.get("enum"), schema.get("const")) {
+            (Some(_), Some(_)) => return Err(format!("The schema at '{}' has both an enum and a const", path)),
+            (Some(Value::Array(values)), None) if !values.is_empty() => Some(values.clone()),
+            (Some(values), None) => return Err(format!("The enum at '{}' has to be a none empty array: {}", path, values)),
+            (None, Some(value)) => Some(vec![value.clone()]),
+            (None, None) => None,
+        };
+        let types = match schema.get("type") {
+            Some(Value::Array(types)) => types.iter()
+                .map(|schema_type| serde_json::from_value(schema_type.clone()))
+                .collect::<Result<Vec<SchemaType>, _>>()
+                .map_err(|error| format!("Invalid type at '{}': {}", path, error))?,
+            Some(schema_type) => vec![serde_json::from_value(schema_type.clone()).map_err(|error| format!("Invalid type at '{}': {}", path, error))?],
+            None if schema.contains_key("properties") => vec![SchemaType::Object],
+            None if schema.contains_key("items") => vec![SchemaType::Array],
+            None if values.is_some() => vec![],
+            None => return Err(format!("The schema at '{}' has no type, enum or const", path)),
+        };
+        if types.is_empty() && values.is_none() {
+            return Err(format!("The schema at '{}' has an empty list of types", path));
+        }
+
+        let required: Vec<&str> = match schema.get("required") {
+            Some(Value::Array(required)) => required.iter()
+                .map(|name| name.as_str().ok_or_else(|| format!("The required properties at '{}' have to be strings", path)))
+                .collect::<Result<_, _>>()?,
+            Some(required) => return Err(format!("The required properties at '{}' have to be an array: {}", path, required)),
+            None => vec![],
+        };
+        let properties = match schema.get("properties") {
+            Some(Value::Object(properties)) => properties.iter()
+                .map(|(name, property_schema)| Ok(Property {
+                    name: name.clone(),
+                    schema: SchemaNode::parse(property_schema, &format!("{}/properties/{}", path, name))?,
+                    is_required: required.contains(&name.as_str()),
+                }))
+                .collect::<Result<Vec<Property>, String>>()?,
+            Some(properties) => return Err(format!("The properties at '{}' have to be an object: {}", path, properties)),
+            None => vec![],
+        };
+        if let Some(name) = required.iter().find(|name| !properties.iter().any(|property| property.name == **name)) {
+            return Err(format!("The required property {} at '{}' has no schema", name, path));
+        }
+
+        let minimal_items = get_usize_keyword(schema, "minItems")?.unwrap_or(0);
+        let maximal_items = get_usize_keyword(schema, "maxItems")?.unwrap_or(minimal_items + DEFAULT_ADDITIONAL_ITEMS);
+        if minimal_items > maximal_items {
+            return Err(format!("Invalid items range at '{}': {}..={}", path, minimal_items, maximal_items));
+        }
+        let items = match schema.get("items") {
+            Some(items) => Some(Box::new(SchemaNode::parse(items, &format!("{}/items", path))?)),
+            None if types.contains(&SchemaType::Array) && maximal_items > 0 =>
+                return Err(format!("The array at '{}' has no items schema", path)),
+            None => None,
+        };
+
+        let default_leaf_options = LeafOptions::default();
+        let minimal_string_length = get_usize_keyword(schema, "minLength")?.unwrap_or(default_leaf_options.minimal_string_length);
+        let minimal_number = get_f64_keyword(schema, "minimum")?.unwrap_or(default_leaf_options.minimal_number);
+        let leaf_options = LeafOptions {
+            minimal_string_length,
+            maximal_string_length: get_usize_keyword(schema, "maxLength")?
+                .unwrap_or_else(|| minimal_string_length.max(default_leaf_options.maximal_string_length)),
+            minimal_number,
+            maximal_number: get_f64_keyword(schema, "maximum")?.unwrap_or_else(|| minimal_number.max(default_leaf_options.maximal_number)),
+            ..default_leaf_options
+        };
+        leaf_options.validate().map_err(|error| format!("Invalid bounds at '{}': {}", path, error))?;
+        if types.contains(&SchemaType::Integer) && leaf_options.minimal_number.ceil() > leaf_options.maximal_number.floor() {
+            return Err(format!("There is no integer at '{}' between {} and {}", path, leaf_options.minimal_number, leaf_options.maximal_number));
+        }
+
+        Ok(SchemaNode { values, types, properties, items, minimal_items, maximal_items, leaf_options })
+    }
+
+    fn get_depth(&self) -> u32 {
+        let properties_depth = self.properties.iter().map(|property| property.schema.get_depth() + 1).max();
+        let items_depth = self.items.as_ref().filter(|_items| self.maximal_items > 0).map(|items| items.get_depth() + 1);
+        properties_depth.into_iter().chain(items_depth).max().unwrap_or(0)
+    }
+
+    fn generate(&self, random_generator: &mut ChaCha8Rng) -> Value {
+        if let Some(values) = &self.values {
+            return randomizer::get_random_value_from_array(random_generator, values).clone();
+        }
+
+        match *randomizer::get_random_value_from_array(random_generator, &self.types) {
+            SchemaType::Null => Value::Null,
+            SchemaType::Boolean => Value::Bool(random_generator.gen()),
+            SchemaType::Integer => {
+                let minimum = self.leaf_options.minimal_number.ceil() as i64;
+                let maximum = self.leaf_options.maximal_number.floor() as i64;
+                Value::from(randomizer::get_random_number_in_range(random_generator, minimum..=maximum))
+            },
+            SchemaType::Number => self.generate_leaf(random_generator, LeafTypeWeights { null: 0, bool: 0, number: 1, string: 0 }),
+            SchemaType::String => self.generate_leaf(random_generator, LeafTypeWeights { null: 0, bool: 0, number: 0, string: 1 }),
+            SchemaType::Array => {
+                let number_of_items = randomizer::get_random_number_in_range(random_generator, self.minimal_items..=self.maximal_items);
+                let items = match &self.items {
+                    Some(items) => (0..number_of_items).map(|_index| items.generate(random_generator)).collect(),
+                    None => vec![],
+                };
+                Value::Array(items)
+            },
+            SchemaType::Object => {
+                let mut map = Map::new();
+                for property in &self.properties {
+                    if property.is_required || random_generator.gen_bool(OPTIONAL_PROPERTY_PROBABILITY) {
+                        map.insert(property.name.clone(), property.schema.generate(random_generator));
+                    }
+                }
+                Value::Object(map)
+            },
+        }
+    }
+
+    fn generate_leaf(&self, random_generator: &mut ChaCha8Rng, type_weights: LeafTypeWeights) -> Value {
+        let leaf_options = LeafOptions { type_weights, ..self.leaf_options.clone() };
+        json_type::get_random_leaf_json(random_generator, &leaf_options)
+    }
+}
+/* #endregion */
+
+/* #region Schema Generator */
+/// Generates random documents that conform to a JSON Schema, supporting the type, enum, const, properties, required,
+/// items, minItems, maxItems, minLength, maxLength, minimum and maximum keywords.
+/// The annotations in [SUPPORTED_KEYWORDS] (such as title or description) are ignored, and any keyword outside of it is rejected.
+#[derive(Debug)]
+pub struct SchemaGenerator {
+    root: SchemaNode,
+    checksum: String,
+}
+
+impl SchemaGenerator {
+    pub fn new(schema: &Value) -> Result<SchemaGenerator, Box<dyn Error + Send + Sync>> {
+        let checksum = Checksum::from_bytes(serde_json::to_string(schema)?.as_bytes()).get_checksum();
+        Ok(SchemaGenerator { root: SchemaNode::parse(schema, "")?, checksum })
+    }
+
+    pub fn read(path_to_schema: &Path) -> Result<SchemaGenerator, Box<dyn Error + Send + Sync>> {
+        let raw_schema = fs::read_to_string(path_to_schema)
+            .map_err(|error| format!("Couldn't read the schema {}: {}", path_to_schema.display(), error))?;
+        SchemaGenerator::new(&serde_json::from_str(&raw_schema)?)
+    }
+
+    /// The checksum of the schema without its formatting, which changes whenever the schema does.
+    pub fn get_checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// The deepest document the schema allows, the root is at depth 0.
+    pub fn get_depth(&self) -> u32 {
+        self.root.get_depth()
+    }
+
+    pub fn generate_json(&self, seed: u64) -> GeneratedJson {
+        self.generate_uncounted_json(seed).count()
+    }
+
+    /// Every record is a document of its own, generated with [json_generator::get_record_seed].
+    pub fn generate_json_lines(&self, number_of_records: u64, seed: u64) -> Vec<GeneratedJson> {
+        self.generate_uncounted_json_lines(number_of_records, seed).into_iter().map(UncountedJson::count).collect()
+    }
+
+    /// Same as [SchemaGenerator::generate_json] without counting the nodes.
+    pub fn generate_uncounted_json(&self, seed: u64) -> UncountedJson {
+        UncountedJson { json: self.root.generate(&mut randomizer::get_seeded_random_generator(seed)), node_counts: None, needle_path: None }
+    }
+
+    pub fn generate_uncounted_json_lines(&self, number_of_records: u64, seed: u64) -> Vec<UncountedJson> {
+        (0..number_of_records)
+            .map(|record_index| self.generate_uncounted_json(json_generator::get_record_seed(seed, record_index)))
+            .collect()
+    }
+}
+/* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    fn get_order_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "status", "items"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 1, "maximum": 100},
+                "status": {"enum": ["open", "closed"]},
+                "note": {"type": ["string", "null"], "maxLength": 4},
+                "items": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": 3,
+                    "items": {
+                        "type": "object",
+                        "required": ["price"],
+                        "properties": {"price": {"type": "number", "minimum": 0.5, "maximum": 2.5}}
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn generated_documents_conform_to_the_schema() {
+        let schema_generator = SchemaGenerator::new(&get_order_schema()).unwrap();
+        assert_eq!(schema_generator.get_depth(), 3);
+        let mut number_of_notes = 0;
+        for seed in 0..200 {
+            let json = schema_generator.generate_json(seed).json;
+            let id = json["id"].as_i64().unwrap();
+            assert!((1..=100).contains(&id), "Invalid id {}", id);
+            assert!(json["status"] == "open" || json["status"] == "closed");
+            match json.get("note") {
+                Some(Value::String(note)) => assert!(note.len() <= 4, "Invalid note {}", note),
+                Some(Value::Null) | None => {},
+                Some(note) => panic!("Invalid note {}", note),
+            }
+            number_of_notes += usize::from(json.get("note").is_some());
+            let items = json["items"].as_array().unwrap();
+            assert!((1..=3).contains(&items.len()));
+            for item in items {
+                let price = item["price"].as_f64().unwrap();
+                assert!((0.5..=2.5).contains(&price), "Invalid price {}", price);
+            }
+        }
+        assert!(number_of_notes > 0 && number_of_notes < 200, "The optional note should be generated only sometimes");
+    }
+
+    #[test]
+    fn same_seed_generates_same_document() {
+        let schema_generator = SchemaGenerator::new(&get_order_schema()).unwrap();
+        assert_eq!(schema_generator.generate_json(42), schema_generator.generate_json(42));
+        let records = schema_generator.generate_json_lines(3, 42);
+        assert_eq!(records[0], schema_generator.generate_json(json_generator::get_record_seed(42, 0)));
+        assert_eq!(records[2].node_counts, json_generator::NodeCounts::count(&records[2].json));
+    }
+
+    #[test]
+    fn invalid_schemas() {
+        for schema in [
+            json!({"type": "array"}),
+            json!({"type": "array", "items": {"type": "null"}, "minItems": 3, "maxItems": 2}),
+            json!({"type": "object", "required": ["a"], "properties": {}}),
+            json!({"type": "integer", "minimum": 1.2, "maximum": 1.8}),
+            json!({"type": "date"}),
+            json!({"oneOf": [{"type": "null"}]}),
+            json!({"enum": []}),
+            json!({"title": "No type"}),
+            json!({"type": "string", "pattern": "^[a-z]+$"}),
+            json!({"type": "number", "exclusiveMinimum": 0}),
+            json!({"type": "integer", "multipleOf": 3}),
+            json!({"type": "array", "items": {"type": "null"}, "uniqueItems": true}),
+            json!({"type": "object", "properties": {"a": {"type": "string", "format": "email"}}}),
+            json!({"type": "object", "minProperties": 1}),
+            json!({"enum": [1, 2], "const": 3}),
+        ] {
+            assert!(SchemaGenerator::new(&schema).is_err(), "Expected an invalid schema: {}", schema);
+        }
+    }
+}
@@ -1,6 +1,11 @@
 /* #region Imports */
 // 3rd Party
 use serde_json::Value;
+
+// Project
+use crate::utils::json_pointer::{self, PathToken};
+use super::query::{Candidate, Query};
+use super::search_match::SearchMatch;
 /* #endregion */
 
 pub fn run(root: &Value, value_to_find: &Value) -> bool {
@@ -39,14 +44,175 @@ pub fn run(root: &Value, value_to_find: &Value) -> bool {
     false
 }
 
+/// The step from the parent to a node, with the index of the parent's step (the root has none).
+type Step<'a> = (Option<usize>, PathToken<'a>);
+
+fn get_json_pointer(steps: &[Step], mut step_index: Option<usize>) -> String {
+    let mut path = vec![];
+    while let Some(index) = step_index {
+        let (parent_step_index, token) = steps[index];
+        path.push(token);
+        step_index = parent_step_index;
+    }
+    path.reverse();
+    json_pointer::to_json_pointer(&path)
+}
+
+/// Same as [run] but returns where the value was found, every node keeps its step so only the path of the match is built.
+pub fn find(root: &Value, value_to_find: &Value) -> Option<SearchMatch> {
+    find_where::<false, _>(root, |candidate| candidate.equals(value_to_find))
+}
+
+/// Same as [find] but returns every match, in the order that the search visits them.
+pub fn find_all(root: &Value, value_to_find: &Value) -> Vec<SearchMatch> {
+    find_all_where::<false, _>(root, |candidate| candidate.equals(value_to_find))
+}
+
+/// Counts the matching keys and values without building their paths.
+pub fn count(root: &Value, value_to_find: &Value) -> u64 {
+    count_where::<false, _>(root, |candidate| candidate.equals(value_to_find))
+}
+
+/// Same as [find] but for the first key or node (including the containers) that the query matches.
+pub fn find_matching(root: &Value, query: &Query) -> Option<SearchMatch> {
+    find_where::<true, _>(root, |candidate| query.matches(candidate))
+}
+
+pub fn find_all_matching(root: &Value, query: &Query) -> Vec<SearchMatch> {
+    find_all_where::<true, _>(root, |candidate| query.matches(candidate))
+}
+
+pub fn count_matching(root: &Value, query: &Query) -> u64 {
+    count_where::<true, _>(root, |candidate| query.matches(candidate))
+}
+
+/// Without `TESTS_CONTAINERS` only the keys and the leaves are tested, since a value to find never equals a container.
+fn find_where<const TESTS_CONTAINERS: bool, F>(root: &Value, is_match: F) -> Option<SearchMatch>
+where F: Fn(Candidate) -> bool {
+    let mut steps: Vec<Step> = vec![];
+    let mut current_nodes: Vec<(&Value, Option<usize>)>;
+    let mut next_level_nodes: Vec<(&Value, Option<usize>)> = vec![(root, None)];
+
+    while !next_level_nodes.is_empty() {
+        current_nodes = next_level_nodes;
+        next_level_nodes = vec![];
+
+        while let Some((current_node, step_index)) = current_nodes.pop() {
+            let is_container = matches!(current_node, Value::Array(_) | Value::Object(_));
+            if (TESTS_CONTAINERS || !is_container) && is_match(Candidate::Value(current_node)) {
+                return Some(SearchMatch { json_pointer: get_json_pointer(&steps, step_index), is_key: false });
+            }
+
+            match current_node {
+                Value::Array(array) => {
+                    for (index, value) in array.iter().enumerate() {
+                        steps.push((step_index, PathToken::Index(index)));
+                        next_level_nodes.push((value, Some(steps.len() - 1)));
+                    }
+                }
+                Value::Object(map) => {
+                    for (key, value) in map {
+                        steps.push((step_index, PathToken::Key(key)));
+                        if is_match(Candidate::Key(key)) {
+                            return Some(SearchMatch { json_pointer: get_json_pointer(&steps, Some(steps.len() - 1)), is_key: true });
+                        } else {
+                            next_level_nodes.push((value, Some(steps.len() - 1)));
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    None
+}
+
+fn find_all_where<const TESTS_CONTAINERS: bool, F>(root: &Value, is_match: F) -> Vec<SearchMatch>
+where F: Fn(Candidate) -> bool {
+    let mut search_matches = vec![];
+    let mut steps: Vec<Step> = vec![];
+    let mut current_nodes: Vec<(&Value, Option<usize>)>;
+    let mut next_level_nodes: Vec<(&Value, Option<usize>)> = vec![(root, None)];
+
+    while !next_level_nodes.is_empty() {
+        current_nodes = next_level_nodes;
+        next_level_nodes = vec![];
+
+        while let Some((current_node, step_index)) = current_nodes.pop() {
+            let is_container = matches!(current_node, Value::Array(_) | Value::Object(_));
+            if (TESTS_CONTAINERS || !is_container) && is_match(Candidate::Value(current_node)) {
+                search_matches.push(SearchMatch { json_pointer: get_json_pointer(&steps, step_index), is_key: false });
+            }
+
+            match current_node {
+                Value::Array(array) => {
+                    for (index, value) in array.iter().enumerate() {
+                        steps.push((step_index, PathToken::Index(index)));
+                        next_level_nodes.push((value, Some(steps.len() - 1)));
+                    }
+                }
+                Value::Object(map) => {
+                    for (key, value) in map {
+                        steps.push((step_index, PathToken::Key(key)));
+                        if is_match(Candidate::Key(key)) {
+                            search_matches.push(SearchMatch { json_pointer: get_json_pointer(&steps, Some(steps.len() - 1)), is_key: true });
+                        }
+                        next_level_nodes.push((value, Some(steps.len() - 1)));
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    search_matches
+}
+
+fn count_where<const TESTS_CONTAINERS: bool, F>(root: &Value, is_match: F) -> u64
+where F: Fn(Candidate) -> bool {
+    let mut number_of_matches = 0;
+    let mut current_nodes: Vec<&Value>;
+    let mut next_level_nodes: Vec<&Value> = vec![root];
+
+    while !next_level_nodes.is_empty() {
+        current_nodes = next_level_nodes;
+        next_level_nodes = vec![];
+
+        while let Some(current_node) = current_nodes.pop() {
+            let is_container = matches!(current_node, Value::Array(_) | Value::Object(_));
+            if (TESTS_CONTAINERS || !is_container) && is_match(Candidate::Value(current_node)) {
+                number_of_matches += 1;
+            }
+
+            match current_node {
+                Value::Array(array) => next_level_nodes.extend(array),
+                Value::Object(map) => {
+                    for (key, value) in map {
+                        if is_match(Candidate::Key(key)) {
+                            number_of_matches += 1;
+                        }
+                        next_level_nodes.push(value);
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    number_of_matches
+}
+
 #[cfg(test)]
 mod tests {
     /* #region Imports */
     // 3rd Party
     use once_cell::sync::Lazy;
+    use regex::Regex;
     use serde_json::{json, Value};
 
     // Project
+    use crate::search_tree::query::ValueType;
     use super::*;
     /* #endregion */
 
@@ -109,6 +275,52 @@ mod tests {
         expect_to_find(json!("lol"));
     }
 
+    #[test]
+    fn should_find_path() {
+        assert_eq!(find(&MESSLY_JSON, &json!("shimi")), Some(SearchMatch { json_pointer: String::from("/a/b/2"), is_key: false }));
+        assert_eq!(find(&MESSLY_JSON, &json!("g")), Some(SearchMatch { json_pointer: String::from("/e/f/g"), is_key: true }));
+        assert_eq!(find(&MESSLY_JSON, &json!(2)), Some(SearchMatch { json_pointer: String::from("/e/f/g"), is_key: false }));
+        // The levels are scanned from their last node
+        assert_eq!(find(&MESSLY_JSON, &json!("lol")), Some(SearchMatch { json_pointer: String::from("/d/1/1"), is_key: false }));
+        assert_eq!(find(&json!("lol"), &json!("lol")), Some(SearchMatch { json_pointer: String::new(), is_key: false }));
+        assert_eq!(find(&MESSLY_JSON, &json!(false)), None);
+    }
+
+    #[test]
+    fn should_find_all() {
+        let json = json!({"a": ["a", {"a": 1}], "b": "a"});
+        let json_pointers: Vec<(String, bool)> = find_all(&json, &json!("a"))
+            .into_iter()
+            .map(|search_match| (search_match.json_pointer, search_match.is_key))
+            .collect();
+        assert_eq!(json_pointers, vec![
+            (String::from("/a"), true),
+            (String::from("/b"), false),
+            (String::from("/a/1/a"), true),
+            (String::from("/a/0"), false),
+        ]);
+        assert_eq!(count(&json, &json!("a")), 4);
+        assert_eq!(count(&MESSLY_JSON, &json!("lol")), 2);
+        assert!(find_all(&MESSLY_JSON, &json!(false)).is_empty());
+        assert_eq!(count(&MESSLY_JSON, &json!(false)), 0);
+    }
+
+    #[test]
+    fn should_find_matching() {
+        let query = Query::StringPrefix(String::from("l"));
+        assert_eq!(find_matching(&MESSLY_JSON, &query), Some(SearchMatch { json_pointer: String::from("/d/1/1"), is_key: false }));
+        assert_eq!(count_matching(&MESSLY_JSON, &query), 2);
+        assert_eq!(find_matching(&MESSLY_JSON, &Query::Type(ValueType::Object)), Some(SearchMatch { json_pointer: String::new(), is_key: false }));
+        assert_eq!(count_matching(&MESSLY_JSON, &Query::Type(ValueType::Array)), 6);
+        assert_eq!(count_matching(&MESSLY_JSON, &Query::NumberRange { minimum: 0.5, maximum: 2.0 }), 3);
+        let json_pointers: Vec<String> = find_all_matching(&MESSLY_JSON, &Query::KeyRegex(Regex::new("^[bc]$").unwrap()))
+            .into_iter()
+            .map(|search_match| search_match.json_pointer)
+            .collect();
+        assert_eq!(json_pointers, vec![String::from("/a/b"), String::from("/a/c")]);
+        assert_eq!(find_matching(&MESSLY_JSON, &Query::StringRegex(Regex::new("^x").unwrap())), None);
+    }
+
     #[test]
     #[should_panic]
     fn should_not_find_1() {
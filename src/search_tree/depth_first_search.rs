@@ -1,6 +1,11 @@
 /* #region Imports */
 // 3rd Party
 use serde_json::Value;
+
+// Project
+use crate::utils::json_pointer::{self, PathToken};
+use super::query::{Candidate, Query};
+use super::search_match::SearchMatch;
 /* #endregion */
 
 pub fn run(root: &Value, value_to_find: &Value) -> bool {
@@ -29,6 +34,120 @@ pub fn run(root: &Value, value_to_find: &Value) -> bool {
     false
 }
 
+/// Same as [run] but returns where the value was found.
+pub fn find(root: &Value, value_to_find: &Value) -> Option<SearchMatch> {
+    find_with_path::<false, _>(root, &|candidate| candidate.equals(value_to_find), &mut vec![])
+}
+
+/// Same as [find] but returns every match, in the order that the search visits them.
+pub fn find_all(root: &Value, value_to_find: &Value) -> Vec<SearchMatch> {
+    let mut search_matches = vec![];
+    find_all_with_path::<false, _>(root, &|candidate| candidate.equals(value_to_find), &mut vec![], &mut search_matches);
+    search_matches
+}
+
+/// Counts the matching keys and values without building their paths.
+pub fn count(root: &Value, value_to_find: &Value) -> u64 {
+    count_where::<false, _>(root, &|candidate| candidate.equals(value_to_find))
+}
+
+/// Same as [find] but for the first key or node (including the containers) that the query matches.
+pub fn find_matching(root: &Value, query: &Query) -> Option<SearchMatch> {
+    find_with_path::<true, _>(root, &|candidate| query.matches(candidate), &mut vec![])
+}
+
+pub fn find_all_matching(root: &Value, query: &Query) -> Vec<SearchMatch> {
+    let mut search_matches = vec![];
+    find_all_with_path::<true, _>(root, &|candidate| query.matches(candidate), &mut vec![], &mut search_matches);
+    search_matches
+}
+
+pub fn count_matching(root: &Value, query: &Query) -> u64 {
+    count_where::<true, _>(root, &|candidate| query.matches(candidate))
+}
+
+/// Without `TESTS_CONTAINERS` only the keys and the leaves are tested, since a value to find never equals a container.
+fn find_with_path<'a, const TESTS_CONTAINERS: bool, F>(root: &'a Value, is_match: &F, path: &mut Vec<PathToken<'a>>) -> Option<SearchMatch>
+where F: Fn(Candidate) -> bool {
+    let is_container = matches!(root, Value::Array(_) | Value::Object(_));
+    if (TESTS_CONTAINERS || !is_container) && is_match(Candidate::Value(root)) {
+        return Some(SearchMatch { json_pointer: json_pointer::to_json_pointer(path), is_key: false });
+    }
+
+    match root {
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                path.push(PathToken::Index(index));
+                if let Some(search_match) = find_with_path::<TESTS_CONTAINERS, _>(value, is_match, path) {
+                    return Some(search_match);
+                }
+                path.pop();
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                path.push(PathToken::Key(key));
+                if is_match(Candidate::Key(key)) {
+                    return Some(SearchMatch { json_pointer: json_pointer::to_json_pointer(path), is_key: true });
+                }
+                if let Some(search_match) = find_with_path::<TESTS_CONTAINERS, _>(value, is_match, path) {
+                    return Some(search_match);
+                }
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+
+    None
+}
+
+fn find_all_with_path<'a, const TESTS_CONTAINERS: bool, F>(root: &'a Value, is_match: &F, path: &mut Vec<PathToken<'a>>, search_matches: &mut Vec<SearchMatch>)
+where F: Fn(Candidate) -> bool {
+    let is_container = matches!(root, Value::Array(_) | Value::Object(_));
+    if (TESTS_CONTAINERS || !is_container) && is_match(Candidate::Value(root)) {
+        search_matches.push(SearchMatch { json_pointer: json_pointer::to_json_pointer(path), is_key: false });
+    }
+
+    match root {
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                path.push(PathToken::Index(index));
+                find_all_with_path::<TESTS_CONTAINERS, _>(value, is_match, path, search_matches);
+                path.pop();
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                path.push(PathToken::Key(key));
+                if is_match(Candidate::Key(key)) {
+                    search_matches.push(SearchMatch { json_pointer: json_pointer::to_json_pointer(path), is_key: true });
+                }
+                find_all_with_path::<TESTS_CONTAINERS, _>(value, is_match, path, search_matches);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn count_where<const TESTS_CONTAINERS: bool, F>(root: &Value, is_match: &F) -> u64
+where F: Fn(Candidate) -> bool {
+    let children_matches = match root {
+        Value::Array(array) => array.iter().map(|value| count_where::<TESTS_CONTAINERS, _>(value, is_match)).sum(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| u64::from(is_match(Candidate::Key(key))) + count_where::<TESTS_CONTAINERS, _>(value, is_match))
+            .sum(),
+        _ => return u64::from(is_match(Candidate::Value(root))),
+    };
+    if TESTS_CONTAINERS {
+        u64::from(is_match(Candidate::Value(root))) + children_matches
+    } else {
+        children_matches
+    }
+}
+
 #[cfg(test)]
 mod tests {
     /* #region Imports */
@@ -37,6 +156,7 @@ mod tests {
     use serde_json::{ Value, json };
 
     // Project
+    use crate::search_tree::query::ValueType;
     use super::*;
     /* #endregion */
 
@@ -93,6 +213,47 @@ mod tests {
         expect_to_find(json!("lol"));
     }
 
+    #[test]
+    fn should_find_path() {
+        assert_eq!(find(&MESSLY_JSON, &json!("shimi")), Some(SearchMatch { json_pointer: String::from("/a/b/2"), is_key: false }));
+        assert_eq!(find(&MESSLY_JSON, &json!("g")), Some(SearchMatch { json_pointer: String::from("/e/f/g"), is_key: true }));
+        assert_eq!(find(&MESSLY_JSON, &json!(2)), Some(SearchMatch { json_pointer: String::from("/e/f/g"), is_key: false }));
+        assert_eq!(find(&MESSLY_JSON, &json!("lol")), Some(SearchMatch { json_pointer: String::from("/d/1/0"), is_key: false }));
+        assert_eq!(find(&json!({"a/b": {"m~n": 1}}), &json!("m~n")), Some(SearchMatch { json_pointer: String::from("/a~1b/m~0n"), is_key: true }));
+        assert_eq!(find(&MESSLY_JSON, &json!(false)), None);
+    }
+
+    #[test]
+    fn should_find_all() {
+        let json = json!({"a": ["a", {"a": 1}], "b": "a"});
+        let json_pointers: Vec<(String, bool)> = find_all(&json, &json!("a"))
+            .into_iter()
+            .map(|search_match| (search_match.json_pointer, search_match.is_key))
+            .collect();
+        assert_eq!(json_pointers, vec![
+            (String::from("/a"), true),
+            (String::from("/a/0"), false),
+            (String::from("/a/1/a"), true),
+            (String::from("/b"), false),
+        ]);
+        assert_eq!(count(&json, &json!("a")), 4);
+        assert_eq!(count(&MESSLY_JSON, &json!("lol")), 2);
+        assert!(find_all(&MESSLY_JSON, &json!(false)).is_empty());
+        assert_eq!(count(&MESSLY_JSON, &json!(false)), 0);
+    }
+
+    #[test]
+    fn should_find_matching() {
+        let query = Query::StringPrefix(String::from("l"));
+        assert_eq!(find_matching(&MESSLY_JSON, &query), Some(SearchMatch { json_pointer: String::from("/d/1/0"), is_key: false }));
+        assert_eq!(count_matching(&MESSLY_JSON, &query), 2);
+        assert_eq!(find_matching(&MESSLY_JSON, &Query::Type(ValueType::Array)), Some(SearchMatch { json_pointer: String::from("/a/b"), is_key: false }));
+        let is_empty_object = Query::custom(|candidate| matches!(candidate, Candidate::Value(Value::Object(map)) if map.is_empty()));
+        assert_eq!(find_all_matching(&json!({"a": {}, "b": [{}, {"c": 1}]}), &is_empty_object).len(), 2);
+        assert_eq!(count_matching(&MESSLY_JSON, &Query::StringContains(String::from("e"))), 1);
+        assert_eq!(find_matching(&MESSLY_JSON, &Query::NumberRange { minimum: 4.0, maximum: 5.0 }), None);
+    }
+
     #[test]
     #[should_panic]
     fn should_not_find_1() {
@@ -122,4 +283,26 @@ mod tests {
     fn should_not_find_5() {
         expect_to_find(json!("Hello"));
     }
+
+    #[test]
+    fn should_find_in_deep_json() {
+        // Thousands of recursive calls need more stack than the default test thread has in debug builds
+        std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(|| {
+                let mut deep_json = json!("needle");
+                for level in 0..5_000 {
+                    deep_json = if level % 2 == 0 {
+                        Value::Array(vec![json!(0), deep_json])
+                    } else {
+                        Value::Object([(String::from("a"), deep_json)].into_iter().collect())
+                    };
+                }
+                assert!(run(&deep_json, &json!("needle")));
+                assert!(!run(&deep_json, &json!("haystack")));
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
 }
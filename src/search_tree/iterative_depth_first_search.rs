@@ -0,0 +1,178 @@
+/* #region Imports */
+// 3rd Party
+use serde_json::Value;
+
+// Project
+use crate::utils::json_pointer::{self, PathToken};
+use super::search_match::SearchMatch;
+/* #endregion */
+
+/// A key is a separate entry so it is compared after the subtrees of the keys before it, like in [super::depth_first_search::run].
+enum Entry<'a> {
+    Key(&'a str),
+    Node(&'a Value),
+}
+
+impl<'a> Entry<'a> {
+    /// Pushes the children in reverse, so they are popped in the order of the recursive search.
+    fn push_children(node: &'a Value, stack: &mut Vec<Entry<'a>>) {
+        match node {
+            Value::Array(array) => stack.extend(array.iter().rev().map(Entry::Node)),
+            Value::Object(map) => {
+                for (key, value) in map.iter().rev() {
+                    stack.push(Entry::Node(value));
+                    stack.push(Entry::Key(key));
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Same order as [super::depth_first_search::run], but with an explicit stack instead of recursion.
+pub fn run(root: &Value, value_to_find: &Value) -> bool {
+    let mut stack = vec![Entry::Node(root)];
+
+    while let Some(entry) = stack.pop() {
+        match entry {
+            Entry::Key(key) => {
+                if key == value_to_find {
+                    return true;
+                }
+            },
+            Entry::Node(node @ (Value::Array(_) | Value::Object(_))) => Entry::push_children(node, &mut stack),
+            Entry::Node(other) => {
+                if other == value_to_find {
+                    return true;
+                }
+            },
+        }
+    }
+
+    false
+}
+
+/// An entry with the length of its parent's path, and the step to it from the parent (the root has none).
+type Frame<'a> = (Entry<'a>, usize, Option<PathToken<'a>>);
+
+/// Same as [run] but returns where the value was found, the path is truncated back to the parent of every popped entry.
+pub fn find(root: &Value, value_to_find: &Value) -> Option<SearchMatch> {
+    let mut path: Vec<PathToken> = vec![];
+    let mut stack: Vec<Frame> = vec![(Entry::Node(root), 0, None)];
+
+    while let Some((entry, parent_path_length, token)) = stack.pop() {
+        path.truncate(parent_path_length);
+        path.extend(token);
+        match entry {
+            Entry::Key(key) => {
+                if key == value_to_find {
+                    return Some(SearchMatch { json_pointer: json_pointer::to_json_pointer(&path), is_key: true });
+                }
+            },
+            Entry::Node(Value::Array(array)) => {
+                stack.extend(array.iter().enumerate().rev().map(|(index, value)| (Entry::Node(value), path.len(), Some(PathToken::Index(index)))));
+            },
+            Entry::Node(Value::Object(map)) => {
+                for (key, value) in map.iter().rev() {
+                    stack.push((Entry::Node(value), path.len(), Some(PathToken::Key(key))));
+                    stack.push((Entry::Key(key), path.len(), Some(PathToken::Key(key))));
+                }
+            },
+            Entry::Node(other) => {
+                if other == value_to_find {
+                    return Some(SearchMatch { json_pointer: json_pointer::to_json_pointer(&path), is_key: false });
+                }
+            },
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use once_cell::sync::Lazy;
+    use serde_json::{Value, json};
+
+    // Project
+    use crate::search_tree::depth_first_search;
+    use super::*;
+    /* #endregion */
+
+    static MESSLY_JSON: Lazy<Value> = Lazy::new(|| json!({
+        "a": {
+            "b": [
+                0,
+                0.5,
+                "shimi"
+            ],
+            "c": [
+                null
+            ]
+        },
+        "d": [
+            [
+                1,
+                "hey"
+            ],
+            [
+                "lol",
+                "lol"
+            ]
+        ],
+        "e": {
+            "f": {
+                "g": 2
+            },
+            "h": [
+                3,
+                true
+            ]
+        }
+    }));
+
+    #[test]
+    fn should_find() {
+        let mut values_to_find: Vec<Value> = ('a'..='h').map(|letter| json!(letter)).collect();
+        values_to_find.extend((0..=3).map(|number| json!(number)));
+        values_to_find.extend([json!(true), json!(0.5), json!("shimi"), json!("hey"), json!("lol")]);
+        for value_to_find in values_to_find {
+            assert!(run(&MESSLY_JSON, &value_to_find), "Expected to find: {}", value_to_find);
+        }
+
+        for value_to_find in [json!(false), json!(4), json!(1.5), json!("i"), json!("Hello")] {
+            assert!(!run(&MESSLY_JSON, &value_to_find), "Didn't expect to find: {}", value_to_find);
+        }
+    }
+
+    #[test]
+    fn should_find_in_the_recursive_order() {
+        let json = json!({"a": [{"b": "x"}, "b"], "b": 1, "x": ["a"]});
+        for value_to_find in [json!("a"), json!("b"), json!("x"), json!(1), json!("lol"), json!(2)] {
+            assert_eq!(find(&json, &value_to_find), depth_first_search::find(&json, &value_to_find), "Found {} elsewhere", value_to_find);
+            assert_eq!(find(&MESSLY_JSON, &value_to_find), depth_first_search::find(&MESSLY_JSON, &value_to_find), "Found {} elsewhere", value_to_find);
+        }
+        assert_eq!(find(&MESSLY_JSON, &json!("lol")), Some(SearchMatch { json_pointer: String::from("/d/1/0"), is_key: false }));
+        assert_eq!(find(&json, &json!("b")), Some(SearchMatch { json_pointer: String::from("/a/0/b"), is_key: true }));
+    }
+
+    #[test]
+    fn should_find_in_deep_json() {
+        // Unlike the recursive search, the default test thread's stack is enough
+        let mut deep_json = json!("needle");
+        for level in 0..100_000 {
+            deep_json = if level % 2 == 0 {
+                Value::Array(vec![json!(0), deep_json])
+            } else {
+                Value::Object([(String::from("a"), deep_json)].into_iter().collect())
+            };
+        }
+        assert!(run(&deep_json, &json!("needle")));
+        assert!(!run(&deep_json, &json!("haystack")));
+        assert_eq!(find(&deep_json, &json!("needle")).map(|search_match| search_match.json_pointer.len()), Some(100_000 / 2 * "/a/1".len()));
+        // Dropping the deep JSON recurses, which would overflow the stack
+        std::mem::forget(deep_json);
+    }
+}
@@ -0,0 +1,616 @@
+/* #region Imports */
+// Standard
+use std::{error::Error, fmt};
+
+// 3rd Party
+use serde::Deserialize;
+use serde_json::Value;
+/* #endregion */
+
+/* #region JSONPath */
+/// A parsed JSONPath expression (RFC 9535 subset): the root, child and descendant segments,
+/// names, wildcards, indexes, slices, and filters with comparisons, existence tests, `&&`, `||` and `!`.
+/// There are no function extensions, and a query in a comparison has to be singular (only child names and indexes).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct JsonPath {
+    expression: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Child(Vec<Selector>),
+    /// Applies the selectors to the node and to all of its descendants.
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice { start: Option<i64>, end: Option<i64>, step: Option<i64> },
+    Filter(FilterExpression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterExpression {
+    Or(Box<FilterExpression>, Box<FilterExpression>),
+    And(Box<FilterExpression>, Box<FilterExpression>),
+    Not(Box<FilterExpression>),
+    /// The query selects at least one node.
+    Exists(FilterQuery),
+    Compare(Comparable, ComparisonOperator, Comparable),
+}
+
+/// A query in a filter, from the current node (`@`) or from the root (`$`).
+#[derive(Debug, Clone, PartialEq)]
+struct FilterQuery {
+    is_relative: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Comparable {
+    Literal(Value),
+    Query(FilterQuery),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl JsonPath {
+    pub fn parse(expression: &str) -> Result<JsonPath, Box<dyn Error + Send + Sync>> {
+        let mut parser = Parser { characters: expression.chars().collect(), position: 0 };
+        let segments = parser.parse_path()
+            .map_err(|message| format!("Invalid JSONPath {}: {} at position {}", expression, message, parser.position))?;
+        Ok(JsonPath { expression: String::from(expression), segments })
+    }
+
+    /// The selected nodes, in the order of RFC 9535 (the document order of every segment).
+    pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        select_segments(&self.segments, root, root)
+    }
+
+    pub fn get_expression(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl TryFrom<String> for JsonPath {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        JsonPath::parse(&expression)
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.expression)
+    }
+}
+/* #endregion */
+
+/* #region Evaluation */
+fn select_segments<'a>(segments: &[Segment], root: &'a Value, start: &'a Value) -> Vec<&'a Value> {
+    let mut nodes = vec![start];
+    for segment in segments {
+        let mut next_nodes = vec![];
+        for node in nodes {
+            match segment {
+                Segment::Child(selectors) => select_children(selectors, root, node, &mut next_nodes),
+                Segment::Descendant(selectors) => {
+                    // Deep JSONs would overflow the stack of a recursive descent, so the descendants are visited in pre-order with a stack
+                    let mut descendants = vec![node];
+                    while let Some(descendant) = descendants.pop() {
+                        select_children(selectors, root, descendant, &mut next_nodes);
+                        match descendant {
+                            Value::Array(array) => descendants.extend(array.iter().rev()),
+                            Value::Object(map) => descendants.extend(map.values().rev()),
+                            _ => {},
+                        }
+                    }
+                },
+            }
+        }
+        nodes = next_nodes;
+    }
+    nodes
+}
+
+fn select_children<'a>(selectors: &[Selector], root: &'a Value, node: &'a Value, selected: &mut Vec<&'a Value>) {
+    for selector in selectors {
+        match (selector, node) {
+            (Selector::Name(name), Value::Object(map)) => selected.extend(map.get(name)),
+            (Selector::Wildcard, Value::Array(array)) => selected.extend(array),
+            (Selector::Wildcard, Value::Object(map)) => selected.extend(map.values()),
+            (Selector::Index(index), Value::Array(array)) => {
+                let index = if *index < 0 { array.len() as i64 + index } else { *index };
+                if index >= 0 {
+                    selected.extend(array.get(index as usize));
+                }
+            },
+            (Selector::Slice { start, end, step }, Value::Array(array)) => {
+                selected.extend(get_slice_indexes(array.len() as i64, *start, *end, *step).map(|index| &array[index as usize]));
+            },
+            (Selector::Filter(filter), Value::Array(array)) => selected.extend(array.iter().filter(|child| filter.test(root, child))),
+            (Selector::Filter(filter), Value::Object(map)) => selected.extend(map.values().filter(|child| filter.test(root, child))),
+            _ => {},
+        }
+    }
+}
+
+/// The indexes of the slice, as RFC 9535 normalizes them (a negative bound counts from the end).
+fn get_slice_indexes(length: i64, start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Box<dyn Iterator<Item = i64>> {
+    let step = step.unwrap_or(1);
+    let normalize = |index: i64| if index >= 0 { index } else { length + index };
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, length);
+        let upper = normalize(end.unwrap_or(length)).clamp(0, length);
+        Box::new((lower..upper).step_by(step as usize))
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(length - 1)).clamp(-1, length - 1);
+        let lower = normalize(end.unwrap_or(-length - 1)).clamp(-1, length - 1);
+        Box::new((lower + 1..=upper).rev().step_by(step.unsigned_abs() as usize))
+    } else {
+        Box::new(std::iter::empty())
+    }
+}
+
+impl FilterExpression {
+    fn test(&self, root: &Value, current: &Value) -> bool {
+        match self {
+            FilterExpression::Or(left, right) => left.test(root, current) || right.test(root, current),
+            FilterExpression::And(left, right) => left.test(root, current) && right.test(root, current),
+            FilterExpression::Not(expression) => !expression.test(root, current),
+            FilterExpression::Exists(query) => !query.select(root, current).is_empty(),
+            FilterExpression::Compare(left, operator, right) => compare(left.evaluate(root, current), *operator, right.evaluate(root, current)),
+        }
+    }
+}
+
+impl FilterQuery {
+    /// Selects at most one node, so it has a value to compare.
+    fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| matches!(segment, Segment::Child(selectors) if matches!(selectors[..], [Selector::Name(_) | Selector::Index(_)])))
+    }
+
+    fn select<'a>(&self, root: &'a Value, current: &'a Value) -> Vec<&'a Value> {
+        select_segments(&self.segments, root, if self.is_relative { current } else { root })
+    }
+}
+
+impl Comparable {
+    /// None when the singular query selects nothing.
+    fn evaluate<'a>(&'a self, root: &'a Value, current: &'a Value) -> Option<&'a Value> {
+        match self {
+            Comparable::Literal(value) => Some(value),
+            Comparable::Query(query) => match query.select(root, current)[..] {
+                [node] => Some(node),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Numbers are equal by their value, so `1 == 1.0`. Only numbers and strings are ordered.
+fn compare(left: Option<&Value>, operator: ComparisonOperator, right: Option<&Value>) -> bool {
+    let is_equal = || match (left, right) {
+        (None, None) => true,
+        (Some(Value::Number(left)), Some(Value::Number(right))) => left.as_f64() == right.as_f64(),
+        (Some(left), Some(right)) => left == right,
+        _ => false,
+    };
+    let is_less = |left: Option<&Value>, right: Option<&Value>| match (left, right) {
+        (Some(Value::Number(left)), Some(Value::Number(right))) => left.as_f64() < right.as_f64(),
+        (Some(Value::String(left)), Some(Value::String(right))) => left < right,
+        _ => false,
+    };
+    match operator {
+        ComparisonOperator::Equal => is_equal(),
+        ComparisonOperator::NotEqual => !is_equal(),
+        ComparisonOperator::Less => is_less(left, right),
+        ComparisonOperator::LessOrEqual => is_less(left, right) || is_equal(),
+        ComparisonOperator::Greater => is_less(right, left),
+        ComparisonOperator::GreaterOrEqual => is_less(right, left) || is_equal(),
+    }
+}
+/* #endregion */
+
+/* #region Parser */
+struct Parser {
+    characters: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.characters.get(self.position + offset).copied()
+    }
+
+    fn consume(&mut self, expected: &str) -> bool {
+        let is_next = expected.chars().enumerate().all(|(offset, character)| self.peek_at(offset) == Some(character));
+        if is_next {
+            self.position += expected.chars().count();
+        }
+        is_next
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        if self.consume(expected) { Ok(()) } else { Err(format!("Expected '{}'", expected)) }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|character| matches!(character, ' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<Segment>, String> {
+        self.expect("$")?;
+        let segments = self.parse_segments()?;
+        match self.peek() {
+            None => Ok(segments),
+            Some(character) => Err(format!("Unexpected '{}'", character)),
+        }
+    }
+
+    fn parse_segments(&mut self) -> Result<Vec<Segment>, String> {
+        let mut segments = vec![];
+        loop {
+            // Blank space is allowed before a segment, but not after the last one
+            let start = self.position;
+            self.skip_whitespace();
+            if !matches!(self.peek(), Some('.' | '[')) {
+                self.position = start;
+                return Ok(segments);
+            }
+            if self.consume("..") {
+                segments.push(Segment::Descendant(match self.peek() {
+                    Some('[') => self.parse_bracketed_selectors()?,
+                    _ => vec![self.parse_shorthand_selector()?],
+                }));
+            } else if self.consume(".") {
+                segments.push(Segment::Child(vec![self.parse_shorthand_selector()?]));
+            } else {
+                segments.push(Segment::Child(self.parse_bracketed_selectors()?));
+            }
+        }
+    }
+
+    /// `*` or a member name, after `.` or `..`.
+    fn parse_shorthand_selector(&mut self) -> Result<Selector, String> {
+        if self.consume("*") {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.position;
+        while self.peek().is_some_and(|character| character.is_alphanumeric() || character == '_' || !character.is_ascii()) {
+            self.position += 1;
+        }
+        if start == self.position || self.characters[start].is_ascii_digit() {
+            return Err(String::from("Expected a member name or '*'"));
+        }
+        Ok(Selector::Name(self.characters[start..self.position].iter().collect()))
+    }
+
+    fn parse_bracketed_selectors(&mut self) -> Result<Vec<Selector>, String> {
+        self.expect("[")?;
+        let mut selectors = vec![];
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_selector()?);
+            self.skip_whitespace();
+            if self.consume("]") {
+                return Ok(selectors);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, String> {
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            },
+            Some('\'' | '"') => Ok(Selector::Name(self.parse_string()?)),
+            Some('?') => {
+                self.position += 1;
+                self.skip_whitespace();
+                Ok(Selector::Filter(self.parse_or()?))
+            },
+            _ => self.parse_index_or_slice(),
+        }
+    }
+
+    fn parse_index_or_slice(&mut self) -> Result<Selector, String> {
+        let start = self.parse_optional_integer()?;
+        self.skip_whitespace();
+        if !self.consume(":") {
+            return start.map(Selector::Index).ok_or_else(|| String::from("Expected a selector"));
+        }
+        self.skip_whitespace();
+        let end = self.parse_optional_integer()?;
+        self.skip_whitespace();
+        let step = if self.consume(":") {
+            self.skip_whitespace();
+            self.parse_optional_integer()?
+        } else {
+            None
+        };
+        Ok(Selector::Slice { start, end, step })
+    }
+
+    fn parse_optional_integer(&mut self) -> Result<Option<i64>, String> {
+        let start = self.position;
+        self.consume("-");
+        while self.peek().is_some_and(|character| character.is_ascii_digit()) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Ok(None);
+        }
+        let integer: String = self.characters[start..self.position].iter().collect();
+        integer.parse().map(Some).map_err(|_error| format!("Invalid integer {}", integer))
+    }
+
+    /// A single or double quoted string with the JSON escapes.
+    fn parse_string(&mut self) -> Result<String, String> {
+        let quote = self.peek().ok_or("Expected a string")?;
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            let character = self.peek().ok_or("Unterminated string")?;
+            self.position += 1;
+            match character {
+                '\\' => {
+                    let escaped = self.peek().ok_or("Unterminated string")?;
+                    self.position += 1;
+                    string.push(match escaped {
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let code_unit = self.parse_hex_code_unit()?;
+                            let code_point = match code_unit {
+                                0xD800..=0xDBFF => {
+                                    if !self.consume("\\u") {
+                                        return Err(String::from("Expected a low surrogate"));
+                                    }
+                                    match self.parse_hex_code_unit()? {
+                                        low_surrogate @ 0xDC00..=0xDFFF => 0x10000 + ((code_unit - 0xD800) << 10) + (low_surrogate - 0xDC00),
+                                        _ => return Err(String::from("Expected a low surrogate")),
+                                    }
+                                },
+                                0xDC00..=0xDFFF => return Err(String::from("Unexpected low surrogate")),
+                                code_point => code_point,
+                            };
+                            char::from_u32(code_point).ok_or("Invalid unicode escape")?
+                        },
+                        '/' | '\\' => escaped,
+                        escaped if escaped == quote => escaped,
+                        other => return Err(format!("Invalid escape \\{}", other)),
+                    });
+                },
+                character if character == quote => return Ok(string),
+                '\u{0}'..='\u{1f}' => return Err(format!("Unescaped control character {:?} in a string", character)),
+                character => string.push(character),
+            }
+        }
+    }
+
+    /// The 4 hex digits after `\u`.
+    fn parse_hex_code_unit(&mut self) -> Result<u32, String> {
+        let hex = self.characters.get(self.position..self.position + 4).ok_or("Invalid unicode escape")?;
+        if !hex.iter().all(char::is_ascii_hexdigit) {
+            return Err(String::from("Invalid unicode escape"));
+        }
+        self.position += 4;
+        u32::from_str_radix(&hex.iter().collect::<String>(), 16).map_err(|_error| String::from("Invalid unicode escape"))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpression, String> {
+        let mut expression = self.parse_and()?;
+        while self.consume("||") {
+            self.skip_whitespace();
+            expression = FilterExpression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpression, String> {
+        let mut expression = self.parse_unary()?;
+        while self.consume("&&") {
+            self.skip_whitespace();
+            expression = FilterExpression::And(Box::new(expression), Box::new(self.parse_unary()?));
+        }
+        Ok(expression)
+    }
+
+    /// Leaves the position after the trailing whitespace, so the callers can look for an operator.
+    fn parse_unary(&mut self) -> Result<FilterExpression, String> {
+        let expression = if self.consume("!") {
+            self.skip_whitespace();
+            FilterExpression::Not(Box::new(self.parse_unary()?))
+        } else if self.consume("(") {
+            self.skip_whitespace();
+            let expression = self.parse_or()?;
+            self.expect(")")?;
+            expression
+        } else {
+            let left = self.parse_comparable()?;
+            self.skip_whitespace();
+            match (self.parse_comparison_operator(), left) {
+                (Some(operator), left) => {
+                    self.skip_whitespace();
+                    let right = self.parse_comparable()?;
+                    for comparable in [&left, &right] {
+                        if matches!(comparable, Comparable::Query(query) if !query.is_singular()) {
+                            return Err(String::from("A query in a comparison has to be singular"));
+                        }
+                    }
+                    FilterExpression::Compare(left, operator, right)
+                },
+                (None, Comparable::Query(query)) => FilterExpression::Exists(query),
+                (None, Comparable::Literal(_)) => return Err(String::from("Expected a comparison after the literal")),
+            }
+        };
+        self.skip_whitespace();
+        Ok(expression)
+    }
+
+    fn parse_comparison_operator(&mut self) -> Option<ComparisonOperator> {
+        [
+            ("==", ComparisonOperator::Equal),
+            ("!=", ComparisonOperator::NotEqual),
+            ("<=", ComparisonOperator::LessOrEqual),
+            (">=", ComparisonOperator::GreaterOrEqual),
+            ("<", ComparisonOperator::Less),
+            (">", ComparisonOperator::Greater),
+        ].into_iter().find_map(|(symbol, operator)| self.consume(symbol).then_some(operator))
+    }
+
+    fn parse_comparable(&mut self) -> Result<Comparable, String> {
+        match self.peek() {
+            Some(start @ ('@' | '$')) => {
+                self.position += 1;
+                Ok(Comparable::Query(FilterQuery { is_relative: start == '@', segments: self.parse_segments()? }))
+            },
+            Some('\'' | '"') => Ok(Comparable::Literal(Value::String(self.parse_string()?))),
+            Some('-' | '0'..='9') => self.parse_number().map(Comparable::Literal),
+            _ => [("true", Value::Bool(true)), ("false", Value::Bool(false)), ("null", Value::Null)]
+                .into_iter()
+                .find_map(|(keyword, value)| self.consume(keyword).then_some(Comparable::Literal(value)))
+                .ok_or_else(|| String::from("Expected a query or a literal")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.position;
+        while self.peek().is_some_and(|character| character.is_ascii_digit() || matches!(character, '-' | '+' | '.' | 'e' | 'E')) {
+            self.position += 1;
+        }
+        let number: String = self.characters[start..self.position].iter().collect();
+        serde_json::from_str::<serde_json::Number>(&number).map(Value::Number).map_err(|_error| format!("Invalid number {}", number))
+    }
+}
+/* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use once_cell::sync::Lazy;
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    static STORE: Lazy<Value> = Lazy::new(|| json!({
+        "store": {
+            "book": [
+                { "category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95 },
+                { "category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99 },
+                { "category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99 },
+                { "category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99 }
+            ],
+            "bicycle": { "color": "red", "price": 399 }
+        }
+    }));
+
+    fn select(expression: &str) -> Vec<Value> {
+        JsonPath::parse(expression).unwrap().select(&STORE).into_iter().cloned().collect()
+    }
+
+    #[test]
+    fn selects_segments() {
+        assert_eq!(select("$"), vec![STORE.clone()]);
+        assert_eq!(select("$.store.bicycle.color"), vec![json!("red")]);
+        assert_eq!(select("$['store'][\"bicycle\"]['color', 'price']"), vec![json!("red"), json!(399)]);
+        assert_eq!(select("$.store.book[*].author").len(), 4);
+        assert_eq!(select("$..author").len(), 4);
+        assert_eq!(select("$.store..price"), vec![json!(399), json!(8.95), json!(12.99), json!(8.99), json!(22.99)]);
+        assert_eq!(select("$..book[2].title"), vec![json!("Moby Dick")]);
+        assert_eq!(select("$..book[-1].title"), vec![json!("The Lord of the Rings")]);
+        assert_eq!(select("$.store.missing"), Vec::<Value>::new());
+        assert_eq!(select("$..*").len(), 27);
+        assert_eq!(select("$ .store\n['bicycle'] .color"), vec![json!("red")]);
+        assert_eq!(select("$..book[?@ .author == 'Herman Melville' ].title"), vec![json!("Moby Dick")]);
+    }
+
+    #[test]
+    fn parses_escapes() {
+        let json = json!({"\u{1F600}": 1, "\u{e9}": 2});
+        assert_eq!(JsonPath::parse("$['\\uD83D\\uDE00']").unwrap().select(&json), vec![&json!(1)]);
+        assert_eq!(JsonPath::parse("$[\"\\u00E9\"]").unwrap().select(&json), vec![&json!(2)]);
+
+        let json = json!({"a'b": 1, "a\"b": 2, "a/\\b": 3, "\u{8}\u{c}\n\r\t": 4});
+        assert_eq!(JsonPath::parse("$['a\\'b']").unwrap().select(&json), vec![&json!(1)]);
+        assert_eq!(JsonPath::parse("$[\"a\\\"b\"]").unwrap().select(&json), vec![&json!(2)]);
+        assert_eq!(JsonPath::parse("$['a\\/\\\\b']").unwrap().select(&json), vec![&json!(3)]);
+        assert_eq!(JsonPath::parse("$['\\b\\f\\n\\r\\t']").unwrap().select(&json), vec![&json!(4)]);
+    }
+
+    #[test]
+    fn selects_slices() {
+        let titles = |expression: &str| select(expression).into_iter().map(|book| book["title"].clone()).collect::<Vec<Value>>();
+        assert_eq!(titles("$.store.book[:2]"), vec![json!("Sayings of the Century"), json!("Sword of Honour")]);
+        assert_eq!(titles("$.store.book[-2:]"), vec![json!("Moby Dick"), json!("The Lord of the Rings")]);
+        assert_eq!(titles("$.store.book[::2]"), vec![json!("Sayings of the Century"), json!("Moby Dick")]);
+        assert_eq!(titles("$.store.book[::-1]")[0], json!("The Lord of the Rings"));
+        assert_eq!(titles("$.store.book[3:1:-1]"), vec![json!("The Lord of the Rings"), json!("Moby Dick")]);
+        assert!(titles("$.store.book[1:3:0]").is_empty());
+    }
+
+    #[test]
+    fn selects_filters() {
+        let titles = |expression: &str| select(expression).into_iter().map(|book| book["title"].clone()).collect::<Vec<Value>>();
+        assert_eq!(titles("$..book[?@.isbn]"), vec![json!("Moby Dick"), json!("The Lord of the Rings")]);
+        assert_eq!(titles("$..book[?(@.price < 10)]"), vec![json!("Sayings of the Century"), json!("Moby Dick")]);
+        assert_eq!(titles("$..book[?@.category == 'fiction' && !(@.price >= 10)]"), vec![json!("Moby Dick")]);
+        assert_eq!(titles("$..book[?@.price > 20 || @.author == \"Nigel Rees\"]").len(), 2);
+        assert_eq!(titles("$..book[?@.price > $.store.bicycle.price]").len(), 0);
+        assert_eq!(titles("$..book[?@.missing != 1]").len(), 4);
+        assert_eq!(select("$.store.bicycle[?@ == 399]"), vec![json!(399)]);
+        assert_eq!(JsonPath::parse("$[?@ == 1]").unwrap().select(&json!([1.0, 2, "1"])), vec![&json!(1.0)]);
+    }
+
+    #[test]
+    fn selects_deep_descendants() {
+        let mut deep_json = json!("needle");
+        for _level in 0..100_000 {
+            deep_json = Value::Array(vec![deep_json]);
+        }
+        assert_eq!(JsonPath::parse("$..[?@ == 'needle']").unwrap().select(&deep_json), vec![&json!("needle")]);
+        // Dropping the deep JSON would overflow the test thread's stack
+        std::mem::forget(deep_json);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in ["", "store", "$.", "$[", "$['a'", "$[?]", "$[?1]", "$.1a", "$[1:2:3:4]", "$..", "$[?@.a ==]", "$ ",
+            "$[?@.* == 1]", "$[?@..a == 1]", "$[?@[0:1] == 1]", "$[?@['a','b'] == 1]", "$[?1 == $[?@]]",
+            "$.a ", "$['\\u+abc']", "$['\\uD83D']", "$['\\uDE00']", "$['\\uD83Dx']", "$['\\q']", "$['\\\"']", "$[\"\\'\"]",
+            "$['a\tb']", "$[\"\u{1}\"]"] {
+            assert!(JsonPath::parse(expression).is_err(), "Parsed the invalid expression {}", expression);
+        }
+        assert_eq!(JsonPath::parse("$..a[0]").unwrap().to_string(), "$..a[0]");
+        assert!(JsonPath::parse("$[?@.a['b'][-1] == $.c[0] && @.*]").is_ok());
+    }
+}
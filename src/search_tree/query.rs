@@ -0,0 +1,122 @@
+/* #region Imports */
+// Standard
+use std::sync::Arc;
+
+// 3rd Party
+use regex::Regex;
+use serde_json::Value;
+/* #endregion */
+
+/// A key or a node that the searches test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Candidate<'a> {
+    Key(&'a str),
+    /// Any node, including the containers.
+    Value(&'a Value),
+}
+
+impl Candidate<'_> {
+    /// A key equal to a string value, or a leaf equal to the value, like the searches by value.
+    pub fn equals(&self, value: &Value) -> bool {
+        match *self {
+            Candidate::Key(key) => value == key,
+            Candidate::Value(Value::Array(_) | Value::Object(_)) => false,
+            Candidate::Value(node) => node == value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl ValueType {
+    pub fn of(value: &Value) -> ValueType {
+        match value {
+            Value::Null => ValueType::Null,
+            Value::Bool(_) => ValueType::Bool,
+            Value::Number(_) => ValueType::Number,
+            Value::String(_) => ValueType::String,
+            Value::Array(_) => ValueType::Array,
+            Value::Object(_) => ValueType::Object,
+        }
+    }
+}
+
+/// What the searches look for, instead of a single value. Only [Query::Equals], [Query::KeyRegex] and [Query::Custom] match keys.
+#[derive(Clone)]
+pub enum Query {
+    /// A key or a leaf equal to the value, the same as the searches by value.
+    Equals(Value),
+    /// A node of the type, including the containers.
+    Type(ValueType),
+    /// A number between the minimum and the maximum (inclusive).
+    NumberRange { minimum: f64, maximum: f64 },
+    StringPrefix(String),
+    StringContains(String),
+    /// A string value that the regular expression matches (anywhere in it, unless it is anchored).
+    StringRegex(Regex),
+    /// A key that the regular expression matches (anywhere in it, unless it is anchored).
+    KeyRegex(Regex),
+    Custom(Arc<dyn Fn(Candidate) -> bool + Send + Sync>),
+}
+
+impl Query {
+    /// A query from a closure, such as `Query::custom(|candidate| matches!(candidate, Candidate::Value(Value::Array(array)) if array.is_empty()))`.
+    pub fn custom<F>(predicate: F) -> Query
+    where F: Fn(Candidate) -> bool + Send + Sync + 'static {
+        Query::Custom(Arc::new(predicate))
+    }
+
+    pub fn matches(&self, candidate: Candidate) -> bool {
+        match (self, candidate) {
+            (Query::Equals(value), candidate) => candidate.equals(value),
+            (Query::Type(value_type), Candidate::Value(node)) => ValueType::of(node) == *value_type,
+            (Query::NumberRange { minimum, maximum }, Candidate::Value(Value::Number(number))) =>
+                number.as_f64().is_some_and(|number| (*minimum..=*maximum).contains(&number)),
+            (Query::StringPrefix(prefix), Candidate::Value(Value::String(string))) => string.starts_with(prefix.as_str()),
+            (Query::StringContains(substring), Candidate::Value(Value::String(string))) => string.contains(substring.as_str()),
+            (Query::StringRegex(regex), Candidate::Value(Value::String(string))) => regex.is_match(string),
+            (Query::KeyRegex(regex), Candidate::Key(key)) => regex.is_match(key),
+            (Query::Custom(predicate), candidate) => predicate(candidate),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn matches_candidates() {
+        let number = json!(2.5);
+        let string = json!("prefix-body");
+        let array = json!([]);
+        assert!(Query::Equals(json!("a")).matches(Candidate::Key("a")));
+        assert!(!Query::Equals(json!([])).matches(Candidate::Value(&array)));
+        assert!(Query::Type(ValueType::Array).matches(Candidate::Value(&array)));
+        assert!(!Query::Type(ValueType::String).matches(Candidate::Key("a")));
+        assert!(Query::NumberRange { minimum: 2.0, maximum: 3.0 }.matches(Candidate::Value(&number)));
+        assert!(!Query::NumberRange { minimum: 3.0, maximum: 4.0 }.matches(Candidate::Value(&number)));
+        assert!(Query::StringPrefix(String::from("prefix")).matches(Candidate::Value(&string)));
+        assert!(!Query::StringPrefix(String::from("prefix")).matches(Candidate::Key("prefix")));
+        assert!(Query::StringContains(String::from("x-b")).matches(Candidate::Value(&string)));
+        assert!(Query::StringRegex(Regex::new("^pre.*y$").unwrap()).matches(Candidate::Value(&string)));
+        assert!(Query::KeyRegex(Regex::new("^id_[0-9]+$").unwrap()).matches(Candidate::Key("id_42")));
+        assert!(!Query::KeyRegex(Regex::new("^id_[0-9]+$").unwrap()).matches(Candidate::Value(&json!("id_42"))));
+        assert!(Query::custom(|candidate| matches!(candidate, Candidate::Key(key) if key.len() == 3)).matches(Candidate::Key("abc")));
+    }
+}
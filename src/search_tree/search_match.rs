@@ -0,0 +1,19 @@
+/* #region Imports */
+// Standard
+use std::fmt;
+/* #endregion */
+
+/// Where a search found the value first, in the order that the search visits the nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// The RFC 6901 JSON Pointer of the matched value, or of the member whose key matched.
+    pub json_pointer: String,
+    pub is_key: bool,
+}
+
+impl fmt::Display for SearchMatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_key { "key" } else { "value" };
+        write!(formatter, "the {} at '{}'", kind, self.json_pointer)
+    }
+}
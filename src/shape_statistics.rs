@@ -0,0 +1,360 @@
+/* #region Imports */
+// Standard
+use std::{error::Error, fmt, fs, path::Path};
+
+// 3rd Party
+use serde::Serialize;
+use serde_json::Value;
+
+// Project
+use crate::{
+    json_generator::{FanOutDistribution, GeneratorOptions},
+    utils::{json_type::{LeafOptions, LeafTypeWeights, NumberProfile, StringProfile}, math_data_collector::MathDataCollector},
+};
+/* #endregion */
+
+/// The leaf type weights are learned as parts per thousand.
+const LEAF_TYPE_WEIGHTS_SCALE: f64 = 1000.0;
+
+/* #region Character Kinds */
+/// Which kinds of characters appear in the keys or the strings of the sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CharacterKinds {
+    /// Quotes, backslashes and control characters.
+    pub has_escaped_characters: bool,
+    pub has_none_ascii_characters: bool,
+}
+
+impl CharacterKinds {
+    fn add(&mut self, text: &str) {
+        self.has_escaped_characters |= text.chars().any(|character| character == '"' || character == '\\' || character.is_control());
+        self.has_none_ascii_characters |= !text.is_ascii();
+    }
+
+    fn get_string_profile(&self) -> StringProfile {
+        match (self.has_escaped_characters, self.has_none_ascii_characters) {
+            (false, false) => StringProfile::Alphabet,
+            (true, false) => StringProfile::Escapes,
+            (false, true) => StringProfile::Unicode,
+            (true, true) => StringProfile::Mixed,
+        }
+    }
+}
+/* #endregion */
+
+/* #region Shape Statistics */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeafTypeCounts {
+    pub null: u64,
+    pub bool: u64,
+    pub number: u64,
+    pub string: u64,
+}
+
+/// The generator parameters that synthesize JSONs similar to a sample.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LearnedParameters {
+    #[serde(rename = "numberOfLetters")]
+    pub number_of_letters: u32,
+    pub depth: u32,
+    #[serde(rename = "numberOfChildren")]
+    pub number_of_children: u32,
+    #[serde(rename = "generatorOptions")]
+    pub generator_options: GeneratorOptions,
+}
+
+/// The shape of a sample JSON, measured without recursion.
+pub struct ShapeStatistics {
+    /// The number of nodes at every depth, the root is at depth 0.
+    pub nodes_per_depth: Vec<u64>,
+    pub leaves_per_depth: Vec<u64>,
+    pub number_of_objects: u64,
+    pub number_of_arrays: u64,
+    pub leaf_type_counts: LeafTypeCounts,
+    /// The number of children of every object and array.
+    pub fan_out: MathDataCollector,
+    /// In characters.
+    pub key_lengths: MathDataCollector,
+    /// In characters.
+    pub string_lengths: MathDataCollector,
+    pub numbers: MathDataCollector,
+    pub has_only_integers: bool,
+    pub key_characters: CharacterKinds,
+    pub string_characters: CharacterKinds,
+}
+
+impl ShapeStatistics {
+    pub fn new(json: &Value) -> ShapeStatistics {
+        let mut statistics = ShapeStatistics {
+            nodes_per_depth: vec!(),
+            leaves_per_depth: vec!(),
+            number_of_objects: 0,
+            number_of_arrays: 0,
+            leaf_type_counts: LeafTypeCounts::default(),
+            fan_out: MathDataCollector::new(),
+            key_lengths: MathDataCollector::new(),
+            string_lengths: MathDataCollector::new(),
+            numbers: MathDataCollector::new(),
+            has_only_integers: true,
+            key_characters: CharacterKinds::default(),
+            string_characters: CharacterKinds::default(),
+        };
+
+        let mut nodes = vec![(json, 0)];
+        while let Some((node, depth)) = nodes.pop() {
+            if statistics.nodes_per_depth.len() == depth {
+                statistics.nodes_per_depth.push(0);
+                statistics.leaves_per_depth.push(0);
+            }
+            statistics.nodes_per_depth[depth] += 1;
+
+            match node {
+                Value::Object(map) => {
+                    statistics.number_of_objects += 1;
+                    statistics.fan_out.add(map.len() as f64);
+                    for (key, child) in map {
+                        statistics.key_lengths.add(key.chars().count() as f64);
+                        statistics.key_characters.add(key);
+                        nodes.push((child, depth + 1));
+                    }
+                },
+                Value::Array(array) => {
+                    statistics.number_of_arrays += 1;
+                    statistics.fan_out.add(array.len() as f64);
+                    nodes.extend(array.iter().map(|child| (child, depth + 1)));
+                },
+                leaf => {
+                    statistics.leaves_per_depth[depth] += 1;
+                    statistics.add_leaf(leaf);
+                },
+            }
+        }
+        statistics
+    }
+
+    /// Parsing is recursive, so the sample keeps the recursion limit of serde_json: a sample deeper than 128 levels is an error.
+    pub fn read(path_to_sample: &Path) -> Result<ShapeStatistics, Box<dyn Error + Send + Sync>> {
+        let raw_sample = fs::read_to_string(path_to_sample)
+            .map_err(|error| format!("Couldn't read the sample {}: {}", path_to_sample.display(), error))?;
+        let sample: Value = serde_json::from_str(&raw_sample)
+            .map_err(|error| format!("Couldn't parse the sample {}: {}", path_to_sample.display(), error))?;
+        Ok(ShapeStatistics::new(&sample))
+    }
+
+    fn add_leaf(&mut self, leaf: &Value) {
+        match leaf {
+            Value::Null => self.leaf_type_counts.null += 1,
+            Value::Bool(_) => self.leaf_type_counts.bool += 1,
+            Value::Number(number) => {
+                self.leaf_type_counts.number += 1;
+                self.has_only_integers &= !number.is_f64();
+                if let Some(number) = number.as_f64() {
+                    self.numbers.add(number);
+                }
+            },
+            Value::String(string) => {
+                self.leaf_type_counts.string += 1;
+                self.string_lengths.add(string.chars().count() as f64);
+                self.string_characters.add(string);
+            },
+            Value::Array(_) | Value::Object(_) => unreachable!("Containers aren't leaves"),
+        }
+    }
+
+    /// The depth of the deepest node, a lone leaf or an empty container is at depth 0.
+    pub fn get_depth(&self) -> u32 {
+        (self.nodes_per_depth.len() - 1) as u32
+    }
+
+    pub fn get_number_of_nodes(&self) -> u64 {
+        self.nodes_per_depth.iter().sum()
+    }
+
+    pub fn get_number_of_leaves(&self) -> u64 {
+        self.leaves_per_depth.iter().sum()
+    }
+
+    /// Translates the statistics to the generator, the parameters that the generator doesn't have (such as a separate
+    /// fan out per depth) are averaged over the whole sample. The other options keep their defaults.
+    pub fn learn_parameters(&self) -> LearnedParameters {
+        let default_options = GeneratorOptions::default();
+        let default_leaf_options = default_options.leaf.clone();
+        let depth = self.get_depth();
+        let number_of_children = self.fan_out.get_maximum().map_or(0, |maximum| maximum as u32);
+
+        let fan_out = match (self.fan_out.get_minimum(), self.fan_out.get_average()) {
+            (Some(minimum), Some(mean)) if (minimum as u32) < number_of_children =>
+                FanOutDistribution::Geometric { minimum: minimum as u32, maximum: number_of_children, mean },
+            _ => FanOutDistribution::Fixed,
+        };
+
+        let object_ratio = match self.number_of_objects + self.number_of_arrays {
+            0 => default_options.object_ratio,
+            number_of_containers => self.number_of_objects as f64 / number_of_containers as f64,
+        };
+
+        // The leaves above the deepest level ended their branches early
+        let middle_levels = 1..(depth as usize);
+        let number_of_middle_nodes: u64 = self.nodes_per_depth.get(middle_levels.clone()).map_or(0, |nodes| nodes.iter().sum());
+        let number_of_middle_leaves: u64 = self.leaves_per_depth.get(middle_levels).map_or(0, |leaves| leaves.iter().sum());
+        let early_termination_probability = match number_of_middle_nodes {
+            0 => 0.0,
+            number_of_nodes => number_of_middle_leaves as f64 / number_of_nodes as f64,
+        };
+
+        let (minimal_number, maximal_number) = match (self.numbers.get_minimum(), self.numbers.get_maximum()) {
+            (Some(minimum), Some(maximum)) => (minimum, maximum),
+            _ => (default_leaf_options.minimal_number, default_leaf_options.maximal_number),
+        };
+        // The integers are generated within the observed range, the same as the floats
+        let number_profile = if self.leaf_type_counts.number > 0 && self.has_only_integers {
+            NumberProfile::Integers
+        } else {
+            NumberProfile::Float
+        };
+
+        let key_string_profile = match self.key_characters.get_string_profile() {
+            StringProfile::Alphabet => None,
+            key_string_profile => Some(key_string_profile),
+        };
+
+        LearnedParameters {
+            number_of_letters: self.key_lengths.get_average().map_or(1, |average| (average.round() as u32).max(1)),
+            depth,
+            number_of_children,
+            generator_options: GeneratorOptions {
+                leaf: LeafOptions {
+                    type_weights: self.get_leaf_type_weights().unwrap_or(default_leaf_options.type_weights),
+                    minimal_string_length: self.string_lengths.get_minimum().map_or(default_leaf_options.minimal_string_length, |minimum| minimum as usize),
+                    maximal_string_length: self.string_lengths.get_maximum().map_or(default_leaf_options.maximal_string_length, |maximum| maximum as usize),
+                    minimal_number,
+                    maximal_number,
+                    string_profile: self.string_characters.get_string_profile(),
+                    number_profile,
+                },
+                object_ratio,
+                fan_out,
+                early_termination_probability,
+                key_string_profile,
+                ..default_options
+            },
+        }
+    }
+
+    /// Returns none without leaves, a leaf type that appears at all keeps a none zero weight.
+    fn get_leaf_type_weights(&self) -> Option<LeafTypeWeights> {
+        let counts = self.leaf_type_counts;
+        let number_of_leaves = counts.null + counts.bool + counts.number + counts.string;
+        if number_of_leaves == 0 {
+            return None;
+        }
+        let get_weight = |count: u64| match count {
+            0 => 0,
+            count => ((count as f64 * LEAF_TYPE_WEIGHTS_SCALE / number_of_leaves as f64).round() as u32).max(1),
+        };
+        Some(LeafTypeWeights {
+            null: get_weight(counts.null),
+            bool: get_weight(counts.bool),
+            number: get_weight(counts.number),
+            string: get_weight(counts.string),
+        })
+    }
+}
+
+fn format_collector(collector: &MathDataCollector) -> String {
+    match (collector.get_minimum(), collector.get_average(), collector.get_maximum()) {
+        (Some(minimum), Some(average), Some(maximum)) => format!("{} to {} (average {:.2})", minimum, maximum, average),
+        _ => String::from("none"),
+    }
+}
+
+impl fmt::Display for ShapeStatistics {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = self.leaf_type_counts;
+        writeln!(formatter, "Number of nodes: {}, number of leaves: {}", self.get_number_of_nodes(), self.get_number_of_leaves())?;
+        writeln!(formatter, "Nodes per depth: {:?}", self.nodes_per_depth)?;
+        writeln!(formatter, "Leaves per depth: {:?}", self.leaves_per_depth)?;
+        writeln!(formatter, "Objects: {}, arrays: {}", self.number_of_objects, self.number_of_arrays)?;
+        writeln!(formatter, "Fan out: {}", format_collector(&self.fan_out))?;
+        writeln!(formatter, "Leaf types: null {}, bool {}, number {}, string {}", counts.null, counts.bool, counts.number, counts.string)?;
+        writeln!(formatter, "Key lengths: {}", format_collector(&self.key_lengths))?;
+        writeln!(formatter, "String lengths: {}", format_collector(&self.string_lengths))?;
+        write!(formatter, "Numbers: {}", format_collector(&self.numbers))
+    }
+}
+/* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    use crate::json_generator::{Generator, NodeCounts};
+    /* #endregion */
+
+    #[test]
+    fn measure_sample() {
+        let sample = json!({"ab": [1, 2, {"cd": "x\"y", "ef": null}], "gh": true, "ij": {}});
+        let statistics = ShapeStatistics::new(&sample);
+        assert_eq!(statistics.nodes_per_depth, vec![1, 3, 3, 2]);
+        assert_eq!(statistics.leaves_per_depth, vec![0, 1, 2, 2]);
+        assert_eq!((statistics.number_of_objects, statistics.number_of_arrays), (3, 1));
+        assert_eq!(statistics.leaf_type_counts, LeafTypeCounts { null: 1, bool: 1, number: 2, string: 1 });
+        assert_eq!((statistics.fan_out.get_minimum(), statistics.fan_out.get_maximum()), (Some(0.0), Some(3.0)));
+        assert_eq!(statistics.key_lengths.get_average(), Some(2.0));
+        assert_eq!(statistics.string_characters, CharacterKinds { has_escaped_characters: true, has_none_ascii_characters: false });
+
+        let parameters = statistics.learn_parameters();
+        assert_eq!((parameters.number_of_letters, parameters.depth, parameters.number_of_children), (2, 3, 3));
+        let options = &parameters.generator_options;
+        assert_eq!(options.leaf.type_weights, LeafTypeWeights { null: 200, bool: 200, number: 400, string: 200 });
+        assert_eq!(options.leaf.string_profile, StringProfile::Escapes);
+        assert_eq!(options.leaf.number_profile, NumberProfile::Integers);
+        assert_eq!(options.key_string_profile, None);
+        assert_eq!(options.object_ratio, 3.0 / 4.0);
+        assert_eq!(options.early_termination_probability, 3.0 / 6.0);
+        assert_eq!(options.fan_out, FanOutDistribution::Geometric { minimum: 0, maximum: 3, mean: 2.0 });
+        options.validate().unwrap();
+    }
+
+    #[test]
+    fn learn_big_integers() {
+        let parameters = ShapeStatistics::new(&json!([-70_000, 5_000_000, 123_456])).learn_parameters();
+        let leaf_options = &parameters.generator_options.leaf;
+        assert_eq!(leaf_options.number_profile, NumberProfile::Integers);
+        assert_eq!((leaf_options.minimal_number, leaf_options.maximal_number), (-70_000.0, 5_000_000.0));
+
+        let json = Generator::generate_json("ab", 1, 1, 50, 42, &parameters.generator_options).unwrap();
+        let numbers: Vec<&Value> = json.as_array().unwrap().iter().filter(|leaf| leaf.is_number()).collect();
+        assert!(!numbers.is_empty());
+        for number in numbers {
+            assert!(number.as_i64().is_some_and(|number| (-70_000..=5_000_000).contains(&number)), "Expected an integer in the sample range: {}", number);
+        }
+    }
+
+    #[test]
+    fn learned_parameters_reproduce_the_shape() {
+        let options = GeneratorOptions {
+            object_ratio: 0.8,
+            early_termination_probability: 0.2,
+            leaf: LeafOptions { type_weights: LeafTypeWeights { null: 0, bool: 0, number: 1, string: 3 }, ..LeafOptions::default() },
+            ..GeneratorOptions::default()
+        };
+        let sample = Generator::generate_json("abcdefghijklmnopqrstuvwxyz", 5, 6, 4, 3, &options).unwrap();
+        let parameters = ShapeStatistics::new(&sample).learn_parameters();
+        assert_eq!((parameters.number_of_letters, parameters.depth, parameters.number_of_children), (5, 6, 4));
+
+        let learned_options = &parameters.generator_options;
+        assert!((learned_options.object_ratio - 0.8).abs() < 0.1, "Object ratio: {}", learned_options.object_ratio);
+        assert!((learned_options.early_termination_probability - 0.2).abs() < 0.1, "Early termination: {}", learned_options.early_termination_probability);
+        assert_eq!((learned_options.leaf.type_weights.null, learned_options.leaf.type_weights.bool), (0, 0));
+
+        let similar_json = Generator::generate_json("abcdefghijklmnopqrstuvwxyz", 5, parameters.depth, parameters.number_of_children, 4, learned_options).unwrap();
+        let similar_statistics = ShapeStatistics::new(&similar_json);
+        assert!(similar_statistics.get_depth() <= parameters.depth);
+        assert_eq!(similar_statistics.get_number_of_nodes(), NodeCounts::count(&similar_json).number_of_nodes);
+    }
+}
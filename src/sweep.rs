@@ -0,0 +1,130 @@
+/* #region Imports */
+// Standard
+use std::{error::Error, fs, path::Path};
+
+// 3rd Party
+use serde::Deserialize;
+/* #endregion */
+
+/* #region Parameter Range */
+/// The values of a single generator parameter: a value, a list of values, or a range such as `{"from": 2, "to": 10, "step": 2}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ParameterRange {
+    Value(u32),
+    Values(Vec<u32>),
+    /// From and to are inclusive.
+    Range {
+        from: u32,
+        to: u32,
+        #[serde(default = "get_default_step")]
+        step: u32,
+    },
+}
+
+fn get_default_step() -> u32 {
+    1
+}
+
+impl ParameterRange {
+    pub fn get_values(&self) -> Result<Vec<u32>, String> {
+        match self {
+            ParameterRange::Value(value) => Ok(vec![*value]),
+            ParameterRange::Values(values) if values.is_empty() => Err(String::from("The list of values is empty")),
+            ParameterRange::Values(values) => Ok(values.clone()),
+            ParameterRange::Range { step: 0, .. } => Err(String::from("The step of the range has to be none zero")),
+            ParameterRange::Range { from, to, .. } if from > to => Err(format!("Invalid range: {}..={}", from, to)),
+            ParameterRange::Range { from, to, step } => Ok((*from..=*to).step_by(*step as usize).collect()),
+        }
+    }
+}
+/* #endregion */
+
+/* #region Sweep Spec */
+/// A single JSON of the sweep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SweepPoint {
+    pub number_of_letters: u32,
+    pub depth: u32,
+    pub number_of_children: u32,
+}
+
+impl SweepPoint {
+    /// The name of the config, such as `numberOfLetters8_depth10_children5`.
+    pub fn get_name(&self) -> String {
+        format!("numberOfLetters{}_depth{}_children{}", self.number_of_letters, self.depth, self.number_of_children)
+    }
+
+    pub fn get_file_name(&self) -> String {
+        format!("generatedJson_{}.json", self.get_name())
+    }
+}
+
+/// The parameters to generate a JSON for every combination of, the other parameters are the same for all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SweepSpec {
+    #[serde(rename = "numberOfLetters")]
+    pub number_of_letters: ParameterRange,
+    pub depth: ParameterRange,
+    #[serde(rename = "numberOfChildren")]
+    pub number_of_children: ParameterRange,
+}
+
+impl SweepSpec {
+    pub fn read(path_to_spec: &Path) -> Result<SweepSpec, Box<dyn Error + Send + Sync>> {
+        let raw_spec = fs::read_to_string(path_to_spec)
+            .map_err(|error| format!("Couldn't read the sweep spec {}: {}", path_to_spec.display(), error))?;
+        Ok(serde_json::from_str(&raw_spec)?)
+    }
+
+    /// Every combination of the parameters, ordered by the number of letters, then the depth and then the number of children.
+    pub fn get_points(&self) -> Result<Vec<SweepPoint>, String> {
+        let all_number_of_letters = self.number_of_letters.get_values().map_err(|error| format!("numberOfLetters: {}", error))?;
+        let depths = self.depth.get_values().map_err(|error| format!("depth: {}", error))?;
+        let all_number_of_children = self.number_of_children.get_values().map_err(|error| format!("numberOfChildren: {}", error))?;
+
+        let mut points = Vec::with_capacity(all_number_of_letters.len() * depths.len() * all_number_of_children.len());
+        for &number_of_letters in &all_number_of_letters {
+            for &depth in &depths {
+                for &number_of_children in &all_number_of_children {
+                    points.push(SweepPoint { number_of_letters, depth, number_of_children });
+                }
+            }
+        }
+        Ok(points)
+    }
+}
+/* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn parameter_ranges() {
+        let spec: SweepSpec = serde_json::from_str(
+            r#"{"numberOfLetters":8,"depth":{"from":2,"to":7,"step":2},"numberOfChildren":[3,5]}"#
+        ).unwrap();
+        let points = spec.get_points().unwrap();
+        let parameters: Vec<(u32, u32, u32)> = points.iter()
+            .map(|point| (point.number_of_letters, point.depth, point.number_of_children))
+            .collect();
+        assert_eq!(parameters, vec![(8, 2, 3), (8, 2, 5), (8, 4, 3), (8, 4, 5), (8, 6, 3), (8, 6, 5)]);
+        assert_eq!(points[0].get_file_name(), "generatedJson_numberOfLetters8_depth2_children3.json");
+    }
+
+    #[test]
+    fn invalid_parameter_ranges() {
+        for raw_spec in [
+            r#"{"numberOfLetters":8,"depth":{"from":5,"to":2},"numberOfChildren":3}"#,
+            r#"{"numberOfLetters":8,"depth":{"from":2,"to":5,"step":0},"numberOfChildren":3}"#,
+            r#"{"numberOfLetters":[],"depth":2,"numberOfChildren":3}"#,
+        ] {
+            let spec: SweepSpec = serde_json::from_str(raw_spec).unwrap();
+            assert!(spec.get_points().is_err(), "Expected an invalid spec: {}", raw_spec);
+        }
+    }
+}
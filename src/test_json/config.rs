@@ -1,27 +1,287 @@
 /* #region Imports */
 // Standard
 use std::{
+    error::Error,
+    fs,
+    io::Write,
     ops::{Deref, DerefMut},
-    path::PathBuf, sync::Arc,
+    path::{Path, PathBuf}, sync::Arc,
 };
 
 // 3rd-Party
 use serde::{Deserialize};
+use serde_json::Value;
+
+// Project
+use crate::{json_generator::{self, Generator, GeneratorOptions, Manifest, NodeCounts, CHARACTER_POLL}, presets::Preset, schema_generator::SchemaGenerator, search_tree::json_path::JsonPath, utils::{checksum::Checksum, json_lines, output_format, randomizer, unicode_escaping_writer::UnicodeEscapingWriter}};
 /* #endregion */
 
 /* #region Config */
+/// With a manifest, the fields that are missing are taken from it.
+/// Without a path, the JSON is generated from the config (and saved to the cache path when there is one).
 #[derive(Debug, Deserialize)]
+#[serde(try_from = "RawConfig")]
 pub struct Config {
     pub name: Arc<String>,
-    pub size: String,
-    pub path: PathBuf,
+    /// The size of the JSON when missing, known after [Config::load_raw_json].
+    pub size: Option<String>,
+    pub path: Option<PathBuf>,
+    pub cache_path: Option<PathBuf>,
+    pub number_of_letters: u32,
+    pub depth: u32,
+    pub number_of_children: u32,
+    pub seed: Option<u64>,
+    /// With a number of records, the file is JSON Lines and it is tested record by record.
+    pub number_of_records: Option<u64>,
+    /// Generates the JSON from this JSON Schema instead of the tree parameters.
+    pub schema_path: Option<PathBuf>,
+    pub schema: Option<Arc<SchemaGenerator>>,
+    pub generator_options: Arc<GeneratorOptions>,
+    /// A generated JSON has every none ASCII character as a `\uXXXX` escape (surrogate pairs for non-BMP characters).
+    pub escape_unicode: bool,
+    /// The expressions that the JSONPath stage evaluates, the stage is skipped without them.
+    pub json_paths: Arc<Vec<JsonPath>>,
+    /// The value that the find all and count stages look for, which is usually in the JSON many times.
+    pub find_all_value: Arc<Value>,
+    pub manifest: Option<Arc<Manifest>>,
+    pub raw: Option<Arc<String>>,
+}
+
+/// The config as it is written in the file, before filling it from the manifest.
+#[derive(Deserialize)]
+struct RawConfig {
+    name: Arc<String>,
+    /// Path to the manifest that the generator saved next to the JSON.
+    manifest: Option<PathBuf>,
+    size: Option<String>,
+    path: Option<PathBuf>,
+    #[serde(rename = "cachePath")]
+    cache_path: Option<PathBuf>,
     #[serde(rename = "numberOfLetters")]
-    pub number_of_letters: u8,
-    pub depth: u8,
+    number_of_letters: Option<u32>,
+    depth: Option<u32>,
     #[serde(rename = "numberOfChildren")]
-    pub number_of_children: u8,
-    #[serde(skip)]
-    pub raw: Option<Arc<String>>,
+    number_of_children: Option<u32>,
+    seed: Option<u64>,
+    #[serde(rename = "numberOfRecords")]
+    number_of_records: Option<u64>,
+    /// Path to a JSON Schema, which replaces numberOfLetters, depth and numberOfChildren.
+    schema: Option<PathBuf>,
+    /// Replaces the preset of the generator options, the numberOfChildren is its number of items.
+    preset: Option<Preset>,
+    #[serde(rename = "generatorOptions")]
+    generator_options: Option<GeneratorOptions>,
+    #[serde(rename = "escapeUnicode")]
+    escape_unicode: Option<bool>,
+    #[serde(rename = "jsonPaths", default)]
+    json_paths: Vec<JsonPath>,
+    /// Null by default, which is a common leaf of the generated JSONs.
+    #[serde(rename = "findAllValue", default)]
+    find_all_value: Value,
+}
+
+fn get_required_field<T>(value: Option<T>, field_name: &str, config_name: &str) -> Result<T, String> {
+    value.ok_or_else(|| format!("Config {} is missing the field {} and has no manifest", config_name, field_name))
+}
+
+impl TryFrom<RawConfig> for Config {
+    type Error = Box<dyn Error + Send + Sync>;
+
+    fn try_from(raw_config: RawConfig) -> Result<Self, Self::Error> {
+        if raw_config.number_of_records == Some(0) {
+            return Err(Box::from(format!("Config {} has to have at least a single record", raw_config.name)));
+        }
+        if raw_config.number_of_records.is_some() && !raw_config.json_paths.is_empty() {
+            return Err(Box::from(format!("Config {} has JSONPaths, which are evaluated only on a single JSON", raw_config.name)));
+        }
+        let Some(path_to_manifest) = raw_config.manifest else {
+            let name = &raw_config.name;
+            let schema = raw_config.schema.as_deref().map(SchemaGenerator::read).transpose()?;
+            let mut generator_options = raw_config.generator_options.unwrap_or_default();
+            generator_options.preset = raw_config.preset.or(generator_options.preset);
+            if schema.is_some() && generator_options.preset.is_some() {
+                return Err(Box::from(format!("Config {} has both a schema and a preset", name)));
+            }
+            let (number_of_letters, depth, number_of_children) = match (&schema, generator_options.preset) {
+                (Some(schema), _) => (0, schema.get_depth(), 0),
+                (None, Some(preset)) => (0, preset.get_depth(), get_required_field(raw_config.number_of_children, "numberOfChildren", name)?),
+                (None, None) => (
+                    get_required_field(raw_config.number_of_letters, "numberOfLetters", name)?,
+                    get_required_field(raw_config.depth, "depth", name)?,
+                    get_required_field(raw_config.number_of_children, "numberOfChildren", name)?,
+                ),
+            };
+            return Ok(Config {
+                size: raw_config.size,
+                path: raw_config.path,
+                cache_path: raw_config.cache_path,
+                number_of_letters,
+                depth,
+                number_of_children,
+                seed: raw_config.seed,
+                number_of_records: raw_config.number_of_records,
+                schema_path: raw_config.schema,
+                schema: schema.map(Arc::new),
+                generator_options: Arc::new(generator_options),
+                escape_unicode: raw_config.escape_unicode.unwrap_or_default(),
+                json_paths: Arc::new(raw_config.json_paths),
+                find_all_value: Arc::new(raw_config.find_all_value),
+                manifest: None,
+                raw: None,
+                name: raw_config.name,
+            });
+        };
+
+        let manifest = Manifest::read(&path_to_manifest)?;
+        let schema_path = raw_config.schema.or_else(|| manifest.schema_path.clone());
+        let schema = schema_path.as_deref().map(SchemaGenerator::read).transpose()?;
+        let mut generator_options = raw_config.generator_options.unwrap_or_else(|| manifest.generator_options.clone());
+        generator_options.preset = raw_config.preset.or(generator_options.preset);
+        Ok(Config {
+            name: raw_config.name,
+            size: raw_config.size.or_else(|| Some(manifest.get_size())),
+            path: raw_config.path.or_else(|| Some(manifest.get_path_to_json(&path_to_manifest))),
+            cache_path: raw_config.cache_path,
+            number_of_letters: raw_config.number_of_letters.unwrap_or(manifest.number_of_letters),
+            depth: raw_config.depth.unwrap_or(manifest.depth),
+            number_of_children: raw_config.number_of_children.unwrap_or(manifest.number_of_children),
+            seed: raw_config.seed.or(Some(manifest.seed)),
+            number_of_records: raw_config.number_of_records.or(manifest.number_of_records),
+            schema_path,
+            schema: schema.map(Arc::new),
+            generator_options: Arc::new(generator_options),
+            escape_unicode: raw_config.escape_unicode.unwrap_or(manifest.escape_unicode),
+            json_paths: Arc::new(raw_config.json_paths),
+            find_all_value: Arc::new(raw_config.find_all_value),
+            manifest: Some(Arc::new(manifest)),
+            raw: None,
+        })
+    }
+}
+
+impl Config {
+    /// Reads the JSON file, or generates the JSON when there is no path (unless the cached one was generated with the same parameters).
+    pub fn load_raw_json(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let raw_json = match &self.path {
+            Some(path) => {
+                let raw_json = fs::read_to_string(path)?;
+                if let Some(manifest) = &self.manifest {
+                    manifest.verify(raw_json.as_bytes())?;
+                }
+                raw_json
+            },
+            None => match self.read_cached_json() {
+                Some(raw_json) => raw_json,
+                None => self.generate_json()?,
+            },
+        };
+
+        self.size.get_or_insert_with(|| json_generator::format_size(raw_json.len() as u64));
+        self.raw = Some(Arc::new(raw_json));
+        Ok(())
+    }
+
+    /// A missing or outdated cache isn't an error, the JSON is generated again instead.
+    fn read_cached_json(&mut self) -> Option<String> {
+        let cache_path = self.cache_path.as_ref()?;
+        let manifest = Manifest::read(&Manifest::get_manifest_path(cache_path)).ok()?;
+        let is_same_json = manifest.number_of_letters == self.number_of_letters
+            && manifest.characters_poll == CHARACTER_POLL
+            && manifest.depth == self.depth
+            && manifest.number_of_children == self.number_of_children
+            && manifest.target_size.is_none()
+            && (self.seed.is_none() || self.seed == Some(manifest.seed))
+            && manifest.number_of_records == self.number_of_records
+            && manifest.escape_unicode == self.escape_unicode
+            && manifest.schema_path == self.schema_path
+            && manifest.schema_checksum.as_deref() == self.schema.as_ref().map(|schema| schema.get_checksum())
+            && manifest.generator_options == *self.generator_options;
+        if !is_same_json {
+            return None;
+        }
+
+        let raw_json = fs::read_to_string(cache_path).ok()?;
+        manifest.verify(raw_json.as_bytes()).ok()?;
+        self.path = Some(cache_path.clone());
+        self.manifest = Some(Arc::new(manifest));
+        Some(raw_json)
+    }
+
+    fn generate_json(&mut self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let seed = self.seed.unwrap_or_else(randomizer::get_random_seed);
+        let (raw_json, node_counts, needle_path) = match self.number_of_records {
+            Some(number_of_records) => {
+                let records = match &self.schema {
+                    Some(schema) => schema.generate_json_lines(number_of_records, seed),
+                    None => Generator::generate_json_lines(
+                        CHARACTER_POLL, self.number_of_letters, self.depth, self.number_of_children, number_of_records, seed, &self.generator_options
+                    )?,
+                };
+                let node_counts = records.iter().map(|record| record.node_counts).sum::<NodeCounts>();
+                let records: Vec<Value> = records.into_iter().map(|record| record.json).collect();
+                (json_lines::to_string(&records)?, node_counts, None)
+            },
+            None => {
+                let generated_json = match &self.schema {
+                    Some(schema) => schema.generate_json(seed),
+                    None => Generator::generate_json_with_summary(
+                        CHARACTER_POLL, self.number_of_letters, self.depth, self.number_of_children, seed, &self.generator_options
+                    )?,
+                };
+                let raw_json = output_format::to_string(&generated_json.json, self.generator_options.output_format)?;
+                (raw_json, generated_json.node_counts, generated_json.needle_path)
+            },
+        };
+
+        let raw_json = if self.escape_unicode {
+            let mut escaped_raw_json = vec!();
+            UnicodeEscapingWriter::new(&mut escaped_raw_json).write_all(raw_json.as_bytes())?;
+            String::from_utf8(escaped_raw_json)?
+        } else {
+            raw_json
+        };
+
+        let checksum = Checksum::from_bytes(raw_json.as_bytes());
+        let file_name = match &self.cache_path {
+            Some(cache_path) => cache_path.file_name().and_then(|file_name| file_name.to_str()).ok_or("Invalid cache path")?.to_string(),
+            None => format!("{}.json", self.name),
+        };
+        let manifest = Manifest {
+            file_name,
+            seed,
+            number_of_letters: self.number_of_letters,
+            characters_poll: String::from(CHARACTER_POLL),
+            depth: self.depth,
+            number_of_children: self.number_of_children,
+            target_size: None,
+            size_in_bytes: checksum.get_size_in_bytes(),
+            number_of_nodes: node_counts.number_of_nodes,
+            number_of_leaves: node_counts.number_of_leaves,
+            checksum: checksum.get_checksum(),
+            escape_unicode: self.escape_unicode,
+            needle_path,
+            number_of_records: self.number_of_records,
+            schema_path: self.schema_path.clone(),
+            schema_checksum: self.schema.as_ref().map(|schema| schema.get_checksum().to_string()),
+            sample_path: None,
+            generator_options: GeneratorOptions::clone(&self.generator_options),
+        };
+
+        if let Some(cache_path) = &self.cache_path {
+            Config::write_cache(cache_path, &raw_json, &manifest)?;
+            self.path = Some(cache_path.clone());
+        }
+        self.manifest = Some(Arc::new(manifest));
+        Ok(raw_json)
+    }
+
+    fn write_cache(cache_path: &Path, raw_json: &str, manifest: &Manifest) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(cache_directory) = cache_path.parent() {
+            fs::create_dir_all(cache_directory)?;
+        }
+        fs::write(cache_path, raw_json)?;
+        manifest.write(&Manifest::get_manifest_path(cache_path))
+    }
 }
 
 /* #endregion */
@@ -30,6 +290,26 @@ pub struct Config {
 #[derive(Debug, Deserialize)]
 pub struct Configs(Vec<Config>);
 
+impl Configs {
+    /// The relative paths (manifest, path, cachePath and schema) are relative to the directory of the configs file.
+    pub fn read(path: &Path) -> Result<Configs, Box<dyn Error + Send + Sync>> {
+        let raw_configs: Vec<RawConfig> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let configs_directory = path.parent().unwrap_or_else(|| Path::new(""));
+        let resolve = |relative_path: Option<PathBuf>| relative_path.map(|relative_path| configs_directory.join(relative_path));
+        let configs = raw_configs
+            .into_iter()
+            .map(|mut raw_config| {
+                raw_config.manifest = resolve(raw_config.manifest);
+                raw_config.path = resolve(raw_config.path);
+                raw_config.cache_path = resolve(raw_config.cache_path);
+                raw_config.schema = resolve(raw_config.schema);
+                Config::try_from(raw_config)
+            })
+            .collect::<Result<Vec<Config>, _>>()?;
+        Ok(Configs(configs))
+    }
+}
+
 impl IntoIterator for Configs {
     type Item = Config;
     type IntoIter = <Vec<Config> as IntoIterator>::IntoIter;
@@ -53,3 +333,193 @@ impl DerefMut for Configs {
     }
 }
 /* #endregion */
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // Standard
+    use std::env;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn generate_config_without_path() {
+        let cache_path = env::temp_dir().join(format!("generated_config_{}.json", randomizer::get_random_seed()));
+        let raw_configs = format!(
+            r#"[{{"name":"Generated","numberOfLetters":3,"depth":4,"numberOfChildren":3,"seed":42,"cachePath":{:?}}}]"#,
+            cache_path
+        );
+
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        let generated_raw_json = configs[0].raw.clone().unwrap();
+        assert_eq!(configs[0].path.as_deref(), Some(cache_path.as_path()));
+        assert_eq!(fs::read_to_string(&cache_path).unwrap(), *generated_raw_json);
+
+        // The second load reads the cache, so it has to stay the same file
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        assert_eq!(configs[0].raw.clone().unwrap(), generated_raw_json);
+        assert_eq!(configs[0].manifest.as_ref().unwrap().seed, 42);
+
+        // A cache of other node name characters is generated again
+        let manifest_path = Manifest::get_manifest_path(&cache_path);
+        let mut manifest = Manifest::read(&manifest_path).unwrap();
+        manifest.characters_poll = String::from("abc");
+        manifest.write(&manifest_path).unwrap();
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        assert_eq!(configs[0].manifest.as_ref().unwrap().characters_poll, CHARACTER_POLL);
+        assert_eq!(Manifest::read(&manifest_path).unwrap().characters_poll, CHARACTER_POLL);
+
+        // So is a JSON that was written with escaped unicode for a config without it
+        let mut manifest = Manifest::read(&manifest_path).unwrap();
+        manifest.escape_unicode = true;
+        manifest.write(&manifest_path).unwrap();
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        assert!(!Manifest::read(&manifest_path).unwrap().escape_unicode);
+
+        fs::remove_file(Manifest::get_manifest_path(&cache_path)).unwrap();
+        fs::remove_file(cache_path).unwrap();
+    }
+
+    #[test]
+    fn read_configs_with_relative_paths() {
+        let configs_directory = env::temp_dir().join(format!("manifest_configs_{}", randomizer::get_random_seed()));
+        let cache_path = configs_directory.join("data").join("generated.json");
+        let mut configs: Configs = serde_json::from_str(&format!(
+            r#"[{{"name":"Generated","numberOfLetters":3,"depth":3,"numberOfChildren":2,"seed":7,"cachePath":{:?}}}]"#,
+            cache_path
+        )).unwrap();
+        configs[0].load_raw_json().unwrap();
+        let generated_raw_json = configs[0].raw.clone().unwrap();
+
+        // Read from another working directory than the configs file
+        let manifest_file_name = Manifest::get_manifest_path(&cache_path).file_name().unwrap().to_str().unwrap().to_string();
+        let configs_path = configs_directory.join("configs.json");
+        fs::write(configs_directory.join("schema.json"), r#"{"type":"array","items":{"type":"integer"}}"#).unwrap();
+        fs::write(&configs_path, format!(
+            r#"[{{"name":"FromManifest","manifest":"data/{}"}},
+                {{"name":"FromPath","numberOfLetters":3,"depth":3,"numberOfChildren":2,"path":"data/generated.json"}},
+                {{"name":"FromCache","numberOfLetters":3,"depth":3,"numberOfChildren":2,"seed":7,"cachePath":"data/generated.json"}},
+                {{"name":"FromSchema","schema":"schema.json","seed":7}}]"#,
+            manifest_file_name
+        )).unwrap();
+        let mut configs = Configs::read(&configs_path).unwrap();
+        for config in configs.iter_mut().take(3) {
+            config.load_raw_json().unwrap();
+            assert_eq!(config.raw.clone().unwrap(), generated_raw_json, "Config {} didn't read the generated JSON", config.name);
+            assert_eq!(config.path.as_deref(), Some(cache_path.as_path()));
+        }
+        assert_eq!(configs[0].seed, Some(7));
+        assert_eq!(configs[3].schema_path.as_deref(), Some(configs_directory.join("schema.json").as_path()));
+        configs[3].load_raw_json().unwrap();
+
+        fs::remove_dir_all(configs_directory).unwrap();
+    }
+
+    #[test]
+    fn generate_escaped_unicode_config() {
+        let cache_path = env::temp_dir().join(format!("escaped_config_{}.json", randomizer::get_random_seed()));
+        let raw_configs = format!(
+            r#"[{{"name":"Escaped","numberOfLetters":3,"depth":3,"numberOfChildren":3,"seed":42,"escapeUnicode":true,"cachePath":{:?},
+                "generatorOptions":{{"leaf":{{"typeWeights":{{"string":1}},"minimalStringLength":20,"stringProfile":"unicode"}}}}}}]"#,
+            cache_path
+        );
+
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        let raw_json = configs[0].raw.clone().unwrap();
+        assert!(raw_json.is_ascii() && raw_json.contains("\\ud8"), "Expected escaped surrogate pairs: {}", raw_json);
+        assert!(configs[0].manifest.as_ref().unwrap().escape_unicode);
+
+        // The escaped cache is read again
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        assert_eq!(configs[0].raw.clone().unwrap(), raw_json);
+        assert_eq!(configs[0].path.as_deref(), Some(cache_path.as_path()));
+
+        fs::remove_file(Manifest::get_manifest_path(&cache_path)).unwrap();
+        fs::remove_file(cache_path).unwrap();
+    }
+
+    #[test]
+    fn generate_json_lines_config() {
+        let mut configs: Configs = serde_json::from_str(
+            r#"[{"name":"Records","numberOfLetters":3,"depth":2,"numberOfChildren":3,"numberOfRecords":5,"seed":42}]"#
+        ).unwrap();
+        configs[0].load_raw_json().unwrap();
+        let records = json_lines::from_str(configs[0].raw.as_ref().unwrap()).unwrap();
+        assert_eq!(records.len(), 5);
+        let manifest = configs[0].manifest.as_ref().unwrap();
+        assert_eq!(manifest.number_of_records, Some(5));
+        assert_eq!(manifest.number_of_nodes, 5 * (1 + 3 + 9));
+
+        assert!(serde_json::from_str::<Configs>(
+            r#"[{"name":"Records","numberOfLetters":3,"depth":2,"numberOfChildren":3,"numberOfRecords":0}]"#
+        ).is_err());
+    }
+
+    #[test]
+    fn generate_schema_config() {
+        let schema_path = env::temp_dir().join(format!("schema_config_{}.json", randomizer::get_random_seed()));
+        let cache_path = env::temp_dir().join(format!("schema_config_cache_{}.json", randomizer::get_random_seed()));
+        fs::write(&schema_path, r#"{"type":"object","required":["a"],"properties":{"a":{"type":"array","items":{"type":"integer"}}}}"#).unwrap();
+        let raw_configs = format!(r#"[{{"name":"Schema","schema":{:?},"seed":42,"cachePath":{:?}}}]"#, schema_path, cache_path);
+
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        assert_eq!(configs[0].depth, 2);
+        configs[0].load_raw_json().unwrap();
+        let json: Value = serde_json::from_str(configs[0].raw.as_ref().unwrap()).unwrap();
+        assert!(json["a"].as_array().unwrap().iter().all(Value::is_i64), "The JSON doesn't conform to the schema: {}", json);
+        assert_eq!(configs[0].manifest.as_ref().unwrap().schema_path.as_deref(), Some(schema_path.as_path()));
+
+        // Editing the schema outdates the cache
+        fs::write(&schema_path, r#"{"type":"object","required":["a"],"properties":{"a":{"type":"array","items":{"type":"string"}}}}"#).unwrap();
+        let mut configs: Configs = serde_json::from_str(&raw_configs).unwrap();
+        configs[0].load_raw_json().unwrap();
+        let json: Value = serde_json::from_str(configs[0].raw.as_ref().unwrap()).unwrap();
+        assert!(json["a"].as_array().unwrap().iter().all(Value::is_string), "The cached JSON was reused after the schema changed: {}", json);
+
+        fs::remove_file(Manifest::get_manifest_path(&cache_path)).unwrap();
+        fs::remove_file(cache_path).unwrap();
+        fs::remove_file(schema_path).unwrap();
+    }
+
+    #[test]
+    fn generate_preset_config() {
+        let raw_configs = r#"[{"name":"Preset","preset":"geoJson","numberOfChildren":5,"seed":42}]"#;
+        let mut configs: Configs = serde_json::from_str(raw_configs).unwrap();
+        assert_eq!((configs[0].number_of_letters, configs[0].depth), (0, Preset::GeoJson.get_depth()));
+        configs[0].load_raw_json().unwrap();
+        let json: Value = serde_json::from_str(configs[0].raw.as_ref().unwrap()).unwrap();
+        assert_eq!(json["type"], "FeatureCollection");
+        assert_eq!(json["features"].as_array().unwrap().len(), 5);
+
+        assert!(serde_json::from_str::<Configs>(r#"[{"name":"Preset","preset":"table"}]"#).is_err());
+    }
+
+    #[test]
+    fn parse_json_paths_config() {
+        let configs: Configs = serde_json::from_str(
+            r#"[{"name":"Paths","numberOfLetters":3,"depth":2,"numberOfChildren":3,"jsonPaths":["$..*","$[?@.a > 1]"]}]"#
+        ).unwrap();
+        assert_eq!(configs[0].json_paths.iter().map(JsonPath::to_string).collect::<Vec<String>>(), vec!["$..*", "$[?@.a > 1]"]);
+        assert_eq!(*configs[0].find_all_value, Value::Null);
+
+        assert!(serde_json::from_str::<Configs>(
+            r#"[{"name":"Paths","numberOfLetters":3,"depth":2,"numberOfChildren":3,"jsonPaths":["$[?]"]}]"#
+        ).is_err());
+        assert!(serde_json::from_str::<Configs>(
+            r#"[{"name":"Paths","numberOfLetters":3,"depth":2,"numberOfChildren":3,"numberOfRecords":2,"jsonPaths":["$..*"]}]"#
+        ).is_err());
+    }
+
+    #[test]
+    fn config_without_path_and_manifest_needs_the_generator_parameters() {
+        assert!(serde_json::from_str::<Configs>(r#"[{"name":"Generated","depth":4,"numberOfChildren":3}]"#).is_err());
+    }
+}
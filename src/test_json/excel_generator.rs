@@ -10,8 +10,8 @@ use xlsxwriter::{Workbook, XlsxError, Worksheet, Format, format::{FormatBorder,
 use strum::IntoEnumIterator;
 
 // Project
-use crate::utils::math_data_collector::MathDataCollector;
-use super::{config::Configs, measurement_types::MeasurementType, measurement::Measurement};
+use crate::{json_generator::NodeCounts, utils::math_data_collector::MathDataCollector};
+use super::{config::Configs, measurement_types::MeasurementType, measurement::Measurement, reporter::Workload};
 /* #endregion */
 
 pub struct ExcelGenerator<'a> {
@@ -24,6 +24,8 @@ pub struct ExcelGenerator<'a> {
     total_test_length: Duration,
     averages_per_jsons: HashMap<Arc<String>, HashMap<MeasurementType, MathDataCollector>>,
     averages_all_jsons: HashMap<MeasurementType, MathDataCollector>,
+    /// The speedups of every test, from the exact durations, since the averages are in whole milliseconds.
+    speedups_per_jsons: HashMap<Arc<String>, MathDataCollector>,
 }
 
 fn get_data_collectors_for_each_test() -> HashMap<MeasurementType, MathDataCollector> {
@@ -48,9 +50,10 @@ impl <'a> ExcelGenerator<'a> {
         format_colorful.set_fg_color(xlsxwriter::prelude::FormatColor::Custom(0x9AA9F6));
 
         let mut averages_per_jsons = HashMap::new();
+        let mut speedups_per_jsons = HashMap::new();
         for json_name in json_names.iter() {
-            let json_name = Arc::clone(json_name);
-            averages_per_jsons.insert(json_name, get_data_collectors_for_each_test());
+            averages_per_jsons.insert(Arc::clone(json_name), get_data_collectors_for_each_test());
+            speedups_per_jsons.insert(Arc::clone(json_name), MathDataCollector::new());
         }
 
         Ok(ExcelGenerator {
@@ -63,15 +66,18 @@ impl <'a> ExcelGenerator<'a> {
             total_test_length,
             averages_per_jsons,
             averages_all_jsons: get_data_collectors_for_each_test(),
+            speedups_per_jsons,
         })
     }
 
     /* #region Adding Data */
-    pub fn append_worksheet(&mut self, worksheet_name: &String, measures: &HashMap<Arc<String>, HashMap<MeasurementType, Measurement>>)
+    pub fn append_worksheet(&mut self, worksheet_name: &str, measures: &HashMap<Arc<String>, HashMap<MeasurementType, Measurement>>,
+        workloads: Option<&HashMap<Arc<String>, Workload>>)
     -> Result<(), Box<dyn Error + Send + Sync>> {
         let mut worksheet = self.workbook.add_worksheet(Some(worksheet_name))?;
 
         let mut test_data_collectors = get_data_collectors_for_each_test();
+        let mut speedup_data_collector = MathDataCollector::new();
         let mut current_row = 0;
 
         for json_name in &self.json_names {
@@ -84,13 +90,62 @@ impl <'a> ExcelGenerator<'a> {
             let mut json_data_collector = MathDataCollector::new();
 
             current_row = ExcelGenerator::add_test_data(MeasurementType::GenerateJson, "Generating JSON", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
-            current_row = ExcelGenerator::add_test_data(MeasurementType::IterateIteratively, "Iterating JSON Iteratively - BFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
-            current_row = ExcelGenerator::add_test_data(MeasurementType::IterateRecursively, "Iterating JSON Recursively - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
-            current_row = ExcelGenerator::add_test_data(MeasurementType::DeserializeJson, "Deserializing JSON", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
-            current_row = ExcelGenerator::add_test_data(MeasurementType::SerializeJson, "Serializing JSON", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+            if test_data.contains_key(&MeasurementType::GenerateJsonInParallel) {
+                // The same JSON is generated again, so it is kept out of the total
+                let mut parallel_data_collector = MathDataCollector::new();
+                current_row = ExcelGenerator::add_test_data(MeasurementType::GenerateJsonInParallel, "Generating JSON In Parallel", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut parallel_data_collector, &mut test_data_collectors)?;
+                let speedup = ExcelGenerator::get_speedup(
+                    test_data.get(&MeasurementType::GenerateJson).and_then(|measurement| *measurement.get_duration()),
+                    test_data.get(&MeasurementType::GenerateJsonInParallel).and_then(|measurement| *measurement.get_duration()),
+                );
+                if let Some(speedup) = speedup {
+                    speedup_data_collector.add(speedup);
+                    self.speedups_per_jsons
+                        .get_mut(json_name)
+                        .ok_or_else(|| format!("speedups_per_jsons doesn't have the given JSON name: {}", json_name))?
+                        .add(speedup);
+                }
+                current_row = self.add_speedup_data(&mut worksheet, current_row, 0, "Generating JSON Speedup", speedup)?;
+            }
+            if test_data.contains_key(&MeasurementType::DeserializeJsonLines) {
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateJsonLinesIteratively, "Iterating JSON Lines Iteratively - BFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateJsonLinesRecursively, "Iterating JSON Lines Recursively - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateJsonLinesWithStack, "Iterating JSON Lines With A Stack - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::DeserializeJsonLines, "Deserializing JSON Lines", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::SerializeJsonLines, "Serializing JSON Lines", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+            } else {
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateIteratively, "Iterating JSON Iteratively - BFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateRecursively, "Iterating JSON Recursively - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::IterateWithStack, "Iterating JSON With A Stack - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::FindAllIteratively, "Finding All Matches Iteratively - BFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::FindAllRecursively, "Finding All Matches Recursively - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::CountIteratively, "Counting Matches Iteratively - BFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::CountRecursively, "Counting Matches Recursively - DFS", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                for (json_path_index, measurement_type) in ExcelGenerator::get_json_path_measurement_types(|measurement_type| test_data.contains_key(measurement_type)) {
+                    let title = format!("Evaluating JSONPath {}", json_path_index + 1);
+                    current_row = ExcelGenerator::add_test_data(measurement_type, &title, &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                }
+                current_row = ExcelGenerator::add_test_data(MeasurementType::DeserializeJson, "Deserializing JSON", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+                current_row = ExcelGenerator::add_test_data(MeasurementType::SerializeJson, "Serializing JSON", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
+            }
             current_row = ExcelGenerator::add_total_test_data(MeasurementType::Total, "Total", &mut worksheet, current_row, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
             current_row = ExcelGenerator::add_test_data(MeasurementType::TotalIncludeContextSwitch, "Total Including Context Switch", &mut worksheet, current_row, test_data, json_name, &self.format_border, &self.format_border_center, &mut self.averages_per_jsons, &mut self.averages_all_jsons, &mut json_data_collector, &mut test_data_collectors)?;
 
+            let workload = workloads.and_then(|workloads| workloads.get(json_name)).copied().unwrap_or_default();
+            current_row = self.add_node_counts_data(&mut worksheet, current_row, "Generated Number Of Nodes", "Generated Number Of Leaves", workload.generated)?;
+            current_row = self.add_node_counts_data(&mut worksheet, current_row, "Tested Number Of Nodes", "Tested Number Of Leaves", workload.tested)?;
+            worksheet.write_string(current_row, 0, "Number Of Matches", Some(&self.format_border))?;
+            if let Some(number_of_matches) = workload.number_of_matches {
+                worksheet.write_number(current_row, 1, number_of_matches as f64, Some(&self.format_border_center))?;
+            }
+            current_row += 1;
+            // As a string, since a f64 cell would round the bigger seeds
+            worksheet.write_string(current_row, 0, "Seed", Some(&self.format_border))?;
+            if let Some(seed) = workload.seed {
+                worksheet.write_string(current_row, 1, &seed.to_string(), Some(&self.format_border_center))?;
+            }
+            current_row += 1;
+
             current_row += 1;
         }
 
@@ -98,10 +153,30 @@ impl <'a> ExcelGenerator<'a> {
         current_row = self.set_colorful_title(&mut worksheet, current_row, 3, "Averages of this Test")?;
 
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Generating JSONs", MeasurementType::GenerateJson, &mut test_data_collectors)?;
+        if ExcelGenerator::has_measurements(&test_data_collectors, MeasurementType::GenerateJsonInParallel) {
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Generating JSONs In Parallel", MeasurementType::GenerateJsonInParallel, &mut test_data_collectors)?;
+            current_row = self.add_speedup_data(&mut worksheet, current_row, 3, "Average Generating JSONs Speedup", speedup_data_collector.get_average())?;
+        }
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSONs Iteratively - BFS", MeasurementType::IterateIteratively, &mut test_data_collectors)?;
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSONs Recursively - DFS", MeasurementType::IterateRecursively, &mut test_data_collectors)?;
+        current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSONs With A Stack - DFS", MeasurementType::IterateWithStack, &mut test_data_collectors)?;
+        current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Finding All Matches In JSONs Iteratively - BFS", MeasurementType::FindAllIteratively, &mut test_data_collectors)?;
+        current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Finding All Matches In JSONs Recursively - DFS", MeasurementType::FindAllRecursively, &mut test_data_collectors)?;
+        current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Counting Matches In JSONs Iteratively - BFS", MeasurementType::CountIteratively, &mut test_data_collectors)?;
+        current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Counting Matches In JSONs Recursively - DFS", MeasurementType::CountRecursively, &mut test_data_collectors)?;
+        for (json_path_index, measurement_type) in ExcelGenerator::get_json_path_measurement_types(|measurement_type| ExcelGenerator::has_measurements(&test_data_collectors, measurement_type.clone())) {
+            let title = format!("Average Evaluating JSONPath {} In JSONs", json_path_index + 1);
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, &title, measurement_type, &mut test_data_collectors)?;
+        }
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Deserializing JSONs", MeasurementType::DeserializeJson, &mut test_data_collectors)?;
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Serializing JSONs", MeasurementType::SerializeJson, &mut test_data_collectors)?;
+        if ExcelGenerator::has_measurements(&test_data_collectors, MeasurementType::DeserializeJsonLines) {
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSON Lines Iteratively - BFS", MeasurementType::IterateJsonLinesIteratively, &mut test_data_collectors)?;
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSON Lines Recursively - DFS", MeasurementType::IterateJsonLinesRecursively, &mut test_data_collectors)?;
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Iterating JSON Lines With A Stack - DFS", MeasurementType::IterateJsonLinesWithStack, &mut test_data_collectors)?;
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Deserializing JSON Lines", MeasurementType::DeserializeJsonLines, &mut test_data_collectors)?;
+            current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Serializing JSON Lines", MeasurementType::SerializeJsonLines, &mut test_data_collectors)?;
+        }
         current_row = self.add_test_average_data(&mut worksheet, current_row, 3, "Average Totals", MeasurementType::Total, &mut test_data_collectors)?;
         self.add_test_average_data(&mut worksheet, current_row, 3, "Average Totals Including Context Switch", MeasurementType::TotalIncludeContextSwitch, &mut test_data_collectors)?;
 
@@ -120,7 +195,7 @@ impl <'a> ExcelGenerator<'a> {
     }
 
     fn add_test_average_data(&self, worksheet: &mut Worksheet, row: u32, column: u16,
-        title: &'static str, measurement_type: MeasurementType,
+        title: &str, measurement_type: MeasurementType,
         test_data_collectors: &mut HashMap<MeasurementType, MathDataCollector>)
     -> Result<u32, Box<dyn Error + Send + Sync>> {
         worksheet.write_string(row, column, title, Some(&self.format_border))?;
@@ -137,7 +212,7 @@ impl <'a> ExcelGenerator<'a> {
     #[allow(clippy::too_many_arguments)]
     fn add_test_data(
         measurement_type: MeasurementType,
-        title: &'static str,
+        title: &str,
         worksheet: &mut Worksheet,
         current_row: u32,
         test_data: &HashMap<MeasurementType, Measurement>,
@@ -158,20 +233,14 @@ impl <'a> ExcelGenerator<'a> {
         worksheet.write_string(current_row, 0, title, Some(format_border))?;
         worksheet.write_number(current_row, 1, value, Some(format_border_center))?;
         json_data_collector.add(value);
+        // Only the first JSONPath index has a collector in advance
         averages_per_jsons
             .get_mut(json_name)
             .ok_or_else(|| format!("averages_per_jsons doesn't have the given JSON name: {}", json_name))?
-            .get_mut(&measurement_type)
-            .ok_or_else(|| format!("averages_per_jsons doesn't have the given measurement type: {:?}", measurement_type))?
-            .add(value);
-        averages_all_jsons
-            .get_mut(&measurement_type)
-            .ok_or_else(|| format!("averages_all_jsons doesn't have the given measurement type: {:?}", measurement_type))?
-            .add(value);
-        test_data_collectors
-            .get_mut(&measurement_type)
-            .ok_or_else(|| format!("test_data_collectors doesn't have the given measurement type: {:?}", measurement_type))?
+            .entry(measurement_type.clone()).or_default()
             .add(value);
+        averages_all_jsons.entry(measurement_type.clone()).or_default().add(value);
+        test_data_collectors.entry(measurement_type).or_default().add(value);
 
         Ok(current_row + 1)
     }
@@ -210,6 +279,67 @@ impl <'a> ExcelGenerator<'a> {
 
         Ok(current_row + 1)
     }
+
+    fn add_node_counts_data(&self, worksheet: &mut Worksheet, row: u32,
+        number_of_nodes_title: &'static str, number_of_leaves_title: &'static str, node_counts: Option<NodeCounts>)
+    -> Result<u32, Box<dyn Error + Send + Sync>> {
+        worksheet.write_string(row, 0, number_of_nodes_title, Some(&self.format_border))?;
+        worksheet.write_string(row + 1, 0, number_of_leaves_title, Some(&self.format_border))?;
+        if let Some(node_counts) = node_counts {
+            worksheet.write_number(row, 1, node_counts.number_of_nodes as f64, Some(&self.format_border_center))?;
+            worksheet.write_number(row + 1, 1, node_counts.number_of_leaves as f64, Some(&self.format_border_center))?;
+        }
+
+        Ok(row + 2)
+    }
+
+    /// The optional measurement types (such as the parallel generation) have rows only when some JSON was measured with them.
+    fn has_measurements(data_collectors: &HashMap<MeasurementType, MathDataCollector>, measurement_type: MeasurementType) -> bool {
+        data_collectors
+            .get(&measurement_type)
+            .is_some_and(|data_collector| data_collector.get_count() > 0)
+    }
+
+    /// Every JSONPath expression is measured separately, until the first index without measurements.
+    fn get_json_path_measurement_types<P>(has_measurements: P) -> Vec<(usize, MeasurementType)>
+    where P: Fn(&MeasurementType) -> bool {
+        (0..)
+            .map(|json_path_index| (json_path_index, MeasurementType::EvaluateJsonPath(json_path_index)))
+            .take_while(|(_json_path_index, measurement_type)| has_measurements(measurement_type))
+            .collect()
+    }
+
+    /* #region Speedup */
+
+    /// How many times the parallel generation is faster than the single threaded one.
+    fn get_speedup(sequential: Option<Duration>, parallel: Option<Duration>) -> Option<f64> {
+        match (sequential, parallel) {
+            (Some(sequential), Some(parallel)) if !parallel.is_zero() => Some(sequential.as_secs_f64() / parallel.as_secs_f64()),
+            _ => None,
+        }
+    }
+
+    /// JSONs without parallel generation are skipped, so they don't dilute the single threaded averages.
+    fn get_average_speedup_of_all_jsons(&self) -> Option<f64> {
+        let mut speedup_data_collector = MathDataCollector::new();
+        for speedups in self.speedups_per_jsons.values() {
+            if let Some(speedup) = speedups.get_average() {
+                speedup_data_collector.add(speedup);
+            }
+        }
+        speedup_data_collector.get_average()
+    }
+
+    fn add_speedup_data(&self, worksheet: &mut Worksheet, row: u32, column: u16, title: &'static str, speedup: Option<f64>)
+    -> Result<u32, Box<dyn Error + Send + Sync>> {
+        worksheet.write_string(row, column, title, Some(&self.format_border))?;
+        if let Some(speedup) = speedup {
+            worksheet.write_number(row, column + 1, speedup, Some(&self.format_border_center))?;
+        }
+
+        Ok(row + 1)
+    }
+    /* #endregion */
     /* #endregion */
 
     /* #region Add summary worksheet */
@@ -224,10 +354,32 @@ impl <'a> ExcelGenerator<'a> {
 
             current_row = self.set_colorful_title(&mut worksheet, current_row, 0, json_name)?;
             current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Generating JSONs", MeasurementType::GenerateJson, test_data)?;
-            current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSONs Iteratively - BFS", MeasurementType::IterateIteratively, test_data)?;
-            current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSONs Recursively - DFS", MeasurementType::IterateRecursively, test_data)?;
-            current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Deserializing JSONs", MeasurementType::DeserializeJson, test_data)?;
-            current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Serializing JSONs", MeasurementType::SerializeJson, test_data)?;
+            if ExcelGenerator::has_measurements(test_data, MeasurementType::GenerateJsonInParallel) {
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Generating JSONs In Parallel", MeasurementType::GenerateJsonInParallel, test_data)?;
+                let speedup = self.speedups_per_jsons.get(json_name).and_then(MathDataCollector::get_average);
+                current_row = self.add_speedup_data(&mut worksheet, current_row, 0, "Average Generating JSONs Speedup", speedup)?;
+            }
+            if ExcelGenerator::has_measurements(test_data, MeasurementType::DeserializeJsonLines) {
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSON Lines Iteratively - BFS", MeasurementType::IterateJsonLinesIteratively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSON Lines Recursively - DFS", MeasurementType::IterateJsonLinesRecursively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSON Lines With A Stack - DFS", MeasurementType::IterateJsonLinesWithStack, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Deserializing JSON Lines", MeasurementType::DeserializeJsonLines, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Serializing JSON Lines", MeasurementType::SerializeJsonLines, test_data)?;
+            } else {
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSONs Iteratively - BFS", MeasurementType::IterateIteratively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSONs Recursively - DFS", MeasurementType::IterateRecursively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Iterating JSONs With A Stack - DFS", MeasurementType::IterateWithStack, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Finding All Matches In JSONs Iteratively - BFS", MeasurementType::FindAllIteratively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Finding All Matches In JSONs Recursively - DFS", MeasurementType::FindAllRecursively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Counting Matches In JSONs Iteratively - BFS", MeasurementType::CountIteratively, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Counting Matches In JSONs Recursively - DFS", MeasurementType::CountRecursively, test_data)?;
+                for (json_path_index, measurement_type) in ExcelGenerator::get_json_path_measurement_types(|measurement_type| ExcelGenerator::has_measurements(test_data, measurement_type.clone())) {
+                    let title = format!("Average Evaluating JSONPath {} In JSONs", json_path_index + 1);
+                    current_row = self.add_average_data(&mut worksheet, current_row, 0, &title, measurement_type, test_data)?;
+                }
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Deserializing JSONs", MeasurementType::DeserializeJson, test_data)?;
+                current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Serializing JSONs", MeasurementType::SerializeJson, test_data)?;
+            }
             current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Totals", MeasurementType::Total, test_data)?;
             current_row = self.add_average_data(&mut worksheet, current_row, 0, "Average Totals Including Context Switch", MeasurementType::TotalIncludeContextSwitch, test_data)?;
 
@@ -237,10 +389,30 @@ impl <'a> ExcelGenerator<'a> {
         current_row = 0;
         current_row = self.set_colorful_title(&mut worksheet, current_row, 3, "Averages of all Tests")?;
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Generating all JSONs", MeasurementType::GenerateJson)?;
+        if ExcelGenerator::has_measurements(&self.averages_all_jsons, MeasurementType::GenerateJsonInParallel) {
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Generating all JSONs In Parallel", MeasurementType::GenerateJsonInParallel)?;
+            current_row = self.add_speedup_data(&mut worksheet, current_row, 3, "Average Generating all JSONs Speedup", self.get_average_speedup_of_all_jsons())?;
+        }
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSONs Iteratively - BFS", MeasurementType::IterateIteratively)?;
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSONs Recursively - DFS", MeasurementType::IterateRecursively)?;
+        current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSONs With A Stack - DFS", MeasurementType::IterateWithStack)?;
+        current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Finding All Matches In all JSONs Iteratively - BFS", MeasurementType::FindAllIteratively)?;
+        current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Finding All Matches In all JSONs Recursively - DFS", MeasurementType::FindAllRecursively)?;
+        current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Counting Matches In all JSONs Iteratively - BFS", MeasurementType::CountIteratively)?;
+        current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Counting Matches In all JSONs Recursively - DFS", MeasurementType::CountRecursively)?;
+        for (json_path_index, measurement_type) in ExcelGenerator::get_json_path_measurement_types(|measurement_type| ExcelGenerator::has_measurements(&self.averages_all_jsons, measurement_type.clone())) {
+            let title = format!("Average Evaluating JSONPath {} In all JSONs", json_path_index + 1);
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, &title, measurement_type)?;
+        }
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Deserializing all JSONs", MeasurementType::DeserializeJson)?;
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Serializing all JSONs", MeasurementType::SerializeJson)?;
+        if ExcelGenerator::has_measurements(&self.averages_all_jsons, MeasurementType::DeserializeJsonLines) {
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSON Lines Iteratively - BFS", MeasurementType::IterateJsonLinesIteratively)?;
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSON Lines Recursively - DFS", MeasurementType::IterateJsonLinesRecursively)?;
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Iterating all JSON Lines With A Stack - DFS", MeasurementType::IterateJsonLinesWithStack)?;
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Deserializing all JSON Lines", MeasurementType::DeserializeJsonLines)?;
+            current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Serializing all JSON Lines", MeasurementType::SerializeJsonLines)?;
+        }
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Totals", MeasurementType::Total)?;
         current_row = self.add_average_average_data(&mut worksheet, current_row, 3, "Average Totals Including Context Switch", MeasurementType::TotalIncludeContextSwitch)?;
 
@@ -252,7 +424,7 @@ impl <'a> ExcelGenerator<'a> {
     }
 
     fn add_average_data(&self, worksheet: &mut Worksheet, row: u32, column: u16,
-        title: &'static str, measurement_type: MeasurementType,
+        title: &str, measurement_type: MeasurementType,
         test_data: &HashMap<MeasurementType, MathDataCollector>)
     -> Result<u32, Box<dyn Error + Send + Sync>> {
         worksheet.write_string(row, column, title, Some(&self.format_border))?;
@@ -267,7 +439,7 @@ impl <'a> ExcelGenerator<'a> {
     }
 
     fn add_average_average_data(&self, worksheet: &mut Worksheet, row: u32, column: u16,
-        title: &'static str, measurement_type: MeasurementType)
+        title: &str, measurement_type: MeasurementType)
     -> Result<u32, Box<dyn Error + Send + Sync>> {
         worksheet.write_string(row, column, title, Some(&self.format_border))?;
         if let Some(value) = self.averages_all_jsons
@@ -290,9 +462,37 @@ impl <'a> ExcelGenerator<'a> {
             current_row = self.set_colorful_title(&mut worksheet, current_row, 0, &config.name)?;
 
             worksheet.write_string(current_row, 0, "Size", Some(&self.format_border))?;
-            worksheet.write_string(current_row, 1, &config.size, Some(&self.format_border))?;
+            worksheet.write_string(current_row, 1, config.size.as_deref().unwrap_or_default(), Some(&self.format_border))?;
+            current_row += 1;
+
+            if let Some(schema_path) = &config.schema_path {
+                worksheet.write_string(current_row, 0, "Schema", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, schema_path.to_str().ok_or("Invalid path to the schema")?, Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            if let Some(sample_path) = config.manifest.as_ref().and_then(|manifest| manifest.sample_path.as_ref()) {
+                worksheet.write_string(current_row, 0, "Learned From", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, sample_path.to_str().ok_or("Invalid path to the sample")?, Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            if let Some(preset) = config.generator_options.preset {
+                worksheet.write_string(current_row, 0, "Preset", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, &preset.to_string(), Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            worksheet.write_string(current_row, 0, "Find All Value", Some(&self.format_border))?;
+            worksheet.write_string(current_row, 1, &config.find_all_value.to_string(), Some(&self.format_border))?;
             current_row += 1;
 
+            for (json_path_index, json_path) in config.json_paths.iter().enumerate() {
+                worksheet.write_string(current_row, 0, &format!("JSONPath {}", json_path_index + 1), Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, json_path.get_expression(), Some(&self.format_border))?;
+                current_row += 1;
+            }
+
             worksheet.write_string(current_row, 0, "Number Of Letters", Some(&self.format_border))?;
             worksheet.write_number(current_row, 1, config.number_of_letters as f64, Some(&self.format_border))?;
             current_row += 1;
@@ -305,10 +505,69 @@ impl <'a> ExcelGenerator<'a> {
             worksheet.write_number(current_row, 1, config.number_of_children as f64, Some(&self.format_border))?;
             current_row += 1;
 
+            worksheet.write_string(current_row, 0, "Fan Out", Some(&self.format_border))?;
+            worksheet.write_string(current_row, 1, &config.generator_options.fan_out.to_string(), Some(&self.format_border))?;
+            current_row += 1;
+
+            worksheet.write_string(current_row, 0, "Shape", Some(&self.format_border))?;
+            worksheet.write_string(current_row, 1, &config.generator_options.shape.to_string(), Some(&self.format_border))?;
+            current_row += 1;
+
+            worksheet.write_string(current_row, 0, "Output Format", Some(&self.format_border))?;
+            worksheet.write_string(current_row, 1, &config.generator_options.output_format.to_string(), Some(&self.format_border))?;
+            current_row += 1;
+
+            worksheet.write_string(current_row, 0, "Early Termination Probability", Some(&self.format_border))?;
+            worksheet.write_number(current_row, 1, config.generator_options.early_termination_probability, Some(&self.format_border))?;
+            current_row += 1;
+
+            worksheet.write_string(current_row, 0, "Number Of Threads", Some(&self.format_border))?;
+            worksheet.write_number(current_row, 1, config.generator_options.number_of_threads as f64, Some(&self.format_border))?;
+            current_row += 1;
+
+            worksheet.write_string(current_row, 0, "Unique Keys", Some(&self.format_border))?;
+            worksheet.write_boolean(current_row, 1, config.generator_options.unique_keys, Some(&self.format_border))?;
+            current_row += 1;
+
+            if let Some(number_of_records) = config.number_of_records {
+                worksheet.write_string(current_row, 0, "Number Of Records", Some(&self.format_border))?;
+                worksheet.write_number(current_row, 1, number_of_records as f64, Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            if let Some(needle) = &config.generator_options.needle {
+                let needle_kind = if needle.is_key { "Key" } else { "Value" };
+                worksheet.write_string(current_row, 0, "Needle", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, &format!("{} {} ({})", needle_kind, needle.value, needle.position), Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            if let Some(needle_path) = config.manifest.as_ref().and_then(|manifest| manifest.needle_path.as_ref()) {
+                worksheet.write_string(current_row, 0, "Needle Path", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, needle_path, Some(&self.format_border))?;
+                current_row += 1;
+            }
+
+            worksheet.write_string(current_row, 0, "Seed", Some(&self.format_border))?;
+            match config.seed {
+                Some(seed) => worksheet.write_string(current_row, 1, &seed.to_string(), Some(&self.format_border))?,
+                None => worksheet.write_string(current_row, 1, "Random (see the seed of every test)", Some(&self.format_border))?,
+            }
+            current_row += 1;
+
             worksheet.write_string(current_row, 0, "Path", Some(&self.format_border))?;
-            worksheet.write_string(current_row, 1, config.path.to_str().ok_or("Invalid path to json file")?, Some(&self.format_border))?;
+            match &config.path {
+                Some(path) => worksheet.write_string(current_row, 1, path.to_str().ok_or("Invalid path to json file")?, Some(&self.format_border))?,
+                None => worksheet.write_string(current_row, 1, "Generated", Some(&self.format_border))?,
+            }
             current_row += 1;
 
+            if let Some(manifest) = &config.manifest {
+                worksheet.write_string(current_row, 0, "Checksum", Some(&self.format_border))?;
+                worksheet.write_string(current_row, 1, &manifest.checksum, Some(&self.format_border))?;
+                current_row += 1;
+            }
+
             current_row += 1;
         }
         Ok(())
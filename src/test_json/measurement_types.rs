@@ -6,10 +6,23 @@ use strum_macros::EnumIter;
 #[derive(Debug, PartialEq, Eq, Hash, Clone, EnumIter)]
 pub enum MeasurementType {
     GenerateJson,
+    GenerateJsonInParallel,
     DeserializeJson,
     IterateIteratively,
     IterateRecursively,
+    IterateWithStack,
+    FindAllIteratively,
+    FindAllRecursively,
+    CountIteratively,
+    CountRecursively,
+    /// The index of the expression in the config.
+    EvaluateJsonPath(usize),
     SerializeJson,
+    DeserializeJsonLines,
+    IterateJsonLinesIteratively,
+    IterateJsonLinesRecursively,
+    IterateJsonLinesWithStack,
+    SerializeJsonLines,
     Total,
     TotalIncludeContextSwitch,
 }
@@ -9,6 +9,7 @@ use once_cell::sync::Lazy;
 use tokio::sync::RwLock;
 
 // Project
+use crate::json_generator::NodeCounts;
 use super::measurement::Measurement;
 use super::measurement_types::MeasurementType;
 /* #endregion */
@@ -16,15 +17,29 @@ use super::measurement_types::MeasurementType;
 pub static REPORT_INSTANCE: Lazy<RwLock<Report>> = Lazy::new(|| RwLock::new(Report::new()));
 
 pub type ReportData = HashMap<String, HashMap<Arc<String>, HashMap<MeasurementType, Measurement>>>;
+pub type WorkloadData = HashMap<String, HashMap<Arc<String>, Workload>>;
+
+/// The number of nodes each test actually worked on, which can differ from what the config implies.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Workload {
+    pub generated: Option<NodeCounts>,
+    pub tested: Option<NodeCounts>,
+    /// The seed that the JSON was generated from, also when it was drawn randomly.
+    pub seed: Option<u64>,
+    /// How many times the find all and count stages found their value.
+    pub number_of_matches: Option<u64>,
+}
 
 pub struct Report {
     measurement_duration: ReportData,
+    workloads: WorkloadData,
 }
 
 impl Report {
     fn new() -> Report {
         Report {
-            measurement_duration: HashMap::new()
+            measurement_duration: HashMap::new(),
+            workloads: HashMap::new(),
         }
     }
 
@@ -49,6 +64,32 @@ impl Report {
         &self.measurement_duration
     }
 
+    pub fn get_workloads(&self) -> &WorkloadData {
+        &self.workloads
+    }
+
+    fn get_workload_mut(&mut self, test_count: String, json_name: Arc<String>) -> &mut Workload {
+        self.workloads
+            .entry(test_count).or_default()
+            .entry(json_name).or_default()
+    }
+
+    pub fn set_generated_node_counts(&mut self, test_count: String, json_name: Arc<String>, node_counts: NodeCounts) {
+        self.get_workload_mut(test_count, json_name).generated = Some(node_counts);
+    }
+
+    pub fn set_seed(&mut self, test_count: String, json_name: Arc<String>, seed: u64) {
+        self.get_workload_mut(test_count, json_name).seed = Some(seed);
+    }
+
+    pub fn set_number_of_matches(&mut self, test_count: String, json_name: Arc<String>, number_of_matches: u64) {
+        self.get_workload_mut(test_count, json_name).number_of_matches = Some(number_of_matches);
+    }
+
+    pub fn set_tested_node_counts(&mut self, test_count: String, json_name: Arc<String>, node_counts: NodeCounts) {
+        self.get_workload_mut(test_count, json_name).tested = Some(node_counts);
+    }
+
     pub fn measure<F, R>(test_count: String, json_name: Arc<String>, measurement_type: MeasurementType, function: F) -> Result<R, String>
     where F: FnOnce() -> R {
         { REPORT_INSTANCE.blocking_write().start_measure(test_count.clone(), Arc::clone(&json_name), measurement_type.clone()); }
@@ -1,33 +1,89 @@
 /* #region Imports */
 // Standard
-use std::{error::Error, sync::Arc};
+use std::{error::Error, fmt, sync::Arc};
 
 // 3rd Party
 use tokio::task::{self, JoinHandle};
+use serde::Deserialize;
 use serde_json::Value;
 
 // Project
-use crate::{json_generator, search_tree::{breadth_first_search, depth_first_search}};
-use super::{reporter::Report, measurement_types::MeasurementType};
+use crate::{json_generator::{self, GeneratorOptions, NodeCounts}, schema_generator::SchemaGenerator, search_tree::{breadth_first_search, depth_first_search, iterative_depth_first_search, json_path::JsonPath, search_match::SearchMatch}, utils::{json_lines, randomizer}};
+use super::{reporter::{Report, REPORT_INSTANCE}, measurement_types::MeasurementType};
 /* #endregion */
 
-static CHARACTER_POLL: &str = "AaBbCcDdEeFfGgHhIiJjKkLlMmNnOoPpQqRrSsTtUuVvWwXxYyZz!@#$%&";
+/// What the iterate stages search for, and whether it is in the tested JSON.
+#[derive(Debug)]
+pub struct SearchTarget {
+    pub value: Value,
+    pub is_planted: bool,
+    /// The JSON Pointer of the planted needle, when it is known.
+    pub needle_path: Option<String>,
+}
+
+impl SearchTarget {
+    /// The search scans the whole tree.
+    pub fn absent(value: Value) -> SearchTarget {
+        SearchTarget { value, is_planted: false, needle_path: None }
+    }
+
+    pub fn planted(value: Value, needle_path: Option<String>) -> SearchTarget {
+        SearchTarget { value, is_planted: true, needle_path }
+    }
+
+    /// Panics when the search result is wrong, with where the search found the value.
+    /// The location is searched again (outside of the measurement) only to report it or to compare it with the needle path,
+    /// so a needle that is found somewhere else (such as a value that was already in the JSON) fails the test too.
+    fn verify<F>(&self, search_name: &str, is_found: bool, find: F)
+    where F: FnOnce() -> Option<SearchMatch> {
+        match (is_found, self.is_planted) {
+            (true, false) => match find() {
+                Some(search_match) => panic!("{} found {} as {}", search_name, self, search_match),
+                None => panic!("{} found {} but couldn't find where", search_name, self),
+            },
+            (false, true) => panic!("{} the tree didn't find {}", search_name, self),
+            (true, true) => if let Some(needle_path) = &self.needle_path {
+                match find() {
+                    Some(search_match) if search_match.json_pointer == *needle_path => {},
+                    Some(search_match) => panic!("{} found {} somewhere else as {}", search_name, self, search_match),
+                    None => panic!("{} found {} but couldn't find where", search_name, self),
+                }
+            },
+            (false, false) => {},
+        }
+    }
+}
+
+impl fmt::Display for SearchTarget {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.is_planted, &self.needle_path) {
+            (true, Some(needle_path)) => write!(formatter, "the needle {} planted at {}", self.value, needle_path),
+            (true, None) => write!(formatter, "the needle {}", self.value),
+            (false, _) => write!(formatter, "the value {} that shouldn't be in it", self.value),
+        }
+    }
+}
+
+type SearchFunction = fn(&Value, &Value) -> bool;
+type FindFunction = fn(&Value, &Value) -> Option<SearchMatch>;
+type FindAllFunction = fn(&Value, &Value) -> Vec<SearchMatch>;
+type CountFunction = fn(&Value, &Value) -> u64;
 
 pub struct RunTestLoop {
     test_count: u32,
-    value_to_search: Arc<Value>,
 }
 
 impl RunTestLoop {
 
-    pub fn new(test_count: u32, value_to_search: Value) -> RunTestLoop {
+    pub fn new(test_count: u32) -> RunTestLoop {
         RunTestLoop {
             test_count,
-            value_to_search: Arc::new(value_to_search),
         }
     }
 
-    pub async fn run_test(&self, json_name: Arc<String>, number_of_letters: u8, depth: u8, number_of_children: u8, raw_json: Arc<String>)
+    /// Without a seed, every test generates a different random JSON.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_test(&self, json_name: Arc<String>, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: Option<u64>, schema: Option<Arc<SchemaGenerator>>, seed: Option<u64>, generator_options: Arc<GeneratorOptions>, raw_json: Arc<String>, search_target: Arc<SearchTarget>, json_paths: Arc<Vec<JsonPath>>, find_all_value: Arc<Value>)
     -> Result<(), Box<dyn Error + Send + Sync>> {
         for counter in 1..=self.test_count {
             let test_name = format!("Test {}", counter);
@@ -37,69 +93,162 @@ impl RunTestLoop {
                 number_of_letters,
                 depth,
                 number_of_children,
-                Arc::clone(&raw_json)
+                number_of_records,
+                schema.clone(),
+                seed.unwrap_or_else(randomizer::get_random_seed),
+                Arc::clone(&generator_options),
+                Arc::clone(&raw_json),
+                Arc::clone(&search_target),
+                Arc::clone(&json_paths),
+                Arc::clone(&find_all_value)
             ).await?;
         }
 
         Ok(())
     }
 
-    async fn run_single_test(&self, test_count: String, json_name: Arc<String>, number_of_letters: u8, depth: u8, number_of_children: u8, raw_json: Arc<String>)
+    #[allow(clippy::too_many_arguments)]
+    async fn run_single_test(&self, test_count: String, json_name: Arc<String>, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: Option<u64>, schema: Option<Arc<SchemaGenerator>>, seed: u64, generator_options: Arc<GeneratorOptions>, raw_json: Arc<String>, search_target: Arc<SearchTarget>, json_paths: Arc<Vec<JsonPath>>, find_all_value: Arc<Value>)
     -> Result<(), Box<dyn Error + Send + Sync>> {
+        { REPORT_INSTANCE.write().await.set_seed(test_count.clone(), Arc::clone(&json_name), seed); }
         Report::async_measure(
             test_count.clone(),
             Arc::clone(&json_name),
             MeasurementType::TotalIncludeContextSwitch,
             self.run_single_test_without_total_measure(
-                test_count, json_name, number_of_letters, depth, number_of_children, raw_json
+                test_count, json_name, number_of_letters, depth, number_of_children, number_of_records, schema, seed, generator_options, raw_json, search_target, json_paths, find_all_value
             )
         ).await?
     }
 
-    async fn run_single_test_without_total_measure(&self, test_count: String, json_name: Arc<String>, number_of_letters: u8, depth: u8, number_of_children: u8, raw_json: Arc<String>)
+    #[allow(clippy::too_many_arguments)]
+    async fn run_single_test_without_total_measure(&self, test_count: String, json_name: Arc<String>, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: Option<u64>, schema: Option<Arc<SchemaGenerator>>, seed: u64, generator_options: Arc<GeneratorOptions>, raw_json: Arc<String>, search_target: Arc<SearchTarget>, json_paths: Arc<Vec<JsonPath>>, find_all_value: Arc<Value>)
     -> Result<(), Box<dyn Error + Send + Sync>> {
-        RunTestLoop::test_generate_json(test_count.clone(), Arc::clone(&json_name), number_of_letters, depth, number_of_children).await???;
+        // The single threaded generation is always measured, so the parallel one can be compared to it
+        let sequential_generator_options = Arc::new(GeneratorOptions { number_of_threads: 1, ..GeneratorOptions::clone(&generator_options) });
+        RunTestLoop::test_generate_json(test_count.clone(), Arc::clone(&json_name), MeasurementType::GenerateJson, number_of_letters, depth, number_of_children, number_of_records, schema.clone(), seed, sequential_generator_options).await???;
+        // A schema or a preset is generated by a single thread
+        if generator_options.number_of_threads > 1 && schema.is_none() && generator_options.preset.is_none() {
+            RunTestLoop::test_generate_json(test_count.clone(), Arc::clone(&json_name), MeasurementType::GenerateJsonInParallel, number_of_letters, depth, number_of_children, number_of_records, None, seed, generator_options).await???;
+        }
+        if let Some(number_of_records) = number_of_records {
+            return RunTestLoop::test_json_lines(test_count, json_name, number_of_records, raw_json, search_target).await;
+        }
         let json = RunTestLoop::test_deserialize_json(test_count.clone(), Arc::clone(&json_name), raw_json).await??;
         let json = Arc::new(json);
-        self.test_iterate_iteratively(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json)).await??;
-        self.test_iterate_recursively(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json)).await??;
+        RunTestLoop::test_iterate_iteratively(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json), Arc::clone(&search_target)).await??;
+        RunTestLoop::test_iterate_recursively(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json), Arc::clone(&search_target)).await??;
+        RunTestLoop::test_iterate_with_stack(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json), search_target).await??;
+        // Every search has to find the same number of matches as the first one
+        let number_of_matches = RunTestLoop::test_find_all(test_count.clone(), Arc::clone(&json_name), MeasurementType::FindAllIteratively, Arc::clone(&json), Arc::clone(&find_all_value)).await??;
+        REPORT_INSTANCE.write().await.set_number_of_matches(test_count.clone(), Arc::clone(&json_name), number_of_matches);
+        let number_of_recursive_matches = RunTestLoop::test_find_all(test_count.clone(), Arc::clone(&json_name), MeasurementType::FindAllRecursively, Arc::clone(&json), Arc::clone(&find_all_value)).await??;
+        assert_eq!(number_of_recursive_matches, number_of_matches, "BFS and DFS found {} a different number of times", find_all_value);
+        for measurement_type in [MeasurementType::CountIteratively, MeasurementType::CountRecursively] {
+            let number_of_counted_matches = RunTestLoop::test_count_matches(test_count.clone(), Arc::clone(&json_name), measurement_type.clone(), Arc::clone(&json), Arc::clone(&find_all_value)).await??;
+            assert_eq!(number_of_counted_matches, number_of_matches, "{:?} counted {} a different number of times than it was found", measurement_type, find_all_value);
+        }
+        if !json_paths.is_empty() {
+            RunTestLoop::test_evaluate_json_paths(test_count.clone(), Arc::clone(&json_name), Arc::clone(&json), json_paths).await??;
+        }
         RunTestLoop::test_serialize_json(test_count, json_name, json).await???;
         Ok(())
     }
 
-    #[allow(clippy::type_complexity)]
-    fn test_generate_json(test_count: String, json_name: Arc<String>, number_of_letters: u8, depth: u8, number_of_children: u8)
-    -> JoinHandle<Result<Result<Value, Box<dyn Error + Send + Sync>>, String>> {
+    /// With a number of records, generates the records of JSON Lines instead of a single JSON.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn test_generate_json(test_count: String, json_name: Arc<String>, measurement_type: MeasurementType, number_of_letters: u32, depth: u32, number_of_children: u32, number_of_records: Option<u64>, schema: Option<Arc<SchemaGenerator>>, seed: u64, generator_options: Arc<GeneratorOptions>)
+    -> JoinHandle<Result<Result<(), Box<dyn Error + Send + Sync>>, String>> {
         task::spawn_blocking(move || {
-            Report::measure(test_count, json_name, MeasurementType::GenerateJson, move ||
-                json_generator::Generator::generate_json(CHARACTER_POLL, number_of_letters, depth, number_of_children)
-            )
+            let is_sequential = measurement_type == MeasurementType::GenerateJson;
+            let generated = Report::measure(test_count.clone(), Arc::clone(&json_name), measurement_type, move || match (schema, number_of_records) {
+                (Some(schema), Some(number_of_records)) => Ok(schema.generate_uncounted_json_lines(number_of_records, seed)),
+                (Some(schema), None) => Ok(vec![schema.generate_uncounted_json(seed)]),
+                (None, Some(number_of_records)) => json_generator::Generator::generate_uncounted_json_lines(json_generator::CHARACTER_POLL, number_of_letters, depth, number_of_children, number_of_records, seed, &generator_options),
+                (None, None) => json_generator::Generator::generate_uncounted_json(json_generator::CHARACTER_POLL, number_of_letters, depth, number_of_children, seed, &generator_options)
+                    .map(|generated_json| vec![generated_json]),
+            })?;
+            // The nodes that weren't counted while generating are counted outside of the measurement
+            match generated {
+                Ok(generated_jsons) if is_sequential => {
+                    let node_counts = generated_jsons.into_iter().map(|generated_json| generated_json.count().node_counts).sum();
+                    REPORT_INSTANCE.blocking_write().set_generated_node_counts(test_count, json_name, node_counts);
+                    Ok(Ok(()))
+                },
+                generated => Ok(generated.map(|_generated_jsons| ())),
+            }
         })
     }
 
     fn test_deserialize_json(test_count: String, json_name: Arc<String>, raw_json: Arc<String>) -> JoinHandle<Result<Value, String>> {
         task::spawn_blocking(move || {
-            Report::measure(test_count, json_name, MeasurementType::DeserializeJson, move ||
-                serde_json::from_str::<Value>(&raw_json).expect("Couldn't parse the input JSON")
-            )
+            let json = Report::measure(test_count.clone(), Arc::clone(&json_name), MeasurementType::DeserializeJson, move || {
+                // Deep JSONs are nested beyond the default recursion limit, the thread has enough stack for them instead
+                let mut deserializer = serde_json::Deserializer::from_str(&raw_json);
+                deserializer.disable_recursion_limit();
+                let json = Value::deserialize(&mut deserializer).expect("Couldn't parse the input JSON");
+                deserializer.end().expect("The input JSON has trailing characters");
+                json
+            })?;
+            REPORT_INSTANCE.blocking_write().set_tested_node_counts(test_count, json_name, NodeCounts::count(&json));
+            Ok(json)
         })
     }
 
-    fn test_iterate_iteratively(&self, test_count: String, json_name: Arc<String>, json: Arc<Value>) -> JoinHandle<Result<(), String>> {
-        let value_to_search = Arc::clone(&self.value_to_search);
+    fn test_iterate_iteratively(test_count: String, json_name: Arc<String>, json: Arc<Value>, search_target: Arc<SearchTarget>) -> JoinHandle<Result<(), String>> {
         task::spawn_blocking(move || {
-            Report::measure(test_count, json_name, MeasurementType::IterateIteratively, move || {
-                assert!(!breadth_first_search::run(&json, &value_to_search), "BFS the tree found value that shouldn't be in it: {}", value_to_search)
-            })
+            let is_found = Report::measure(test_count, json_name, MeasurementType::IterateIteratively, || breadth_first_search::run(&json, &search_target.value))?;
+            search_target.verify("BFS", is_found, || breadth_first_search::find(&json, &search_target.value));
+            Ok(())
         })
     }
 
-    fn test_iterate_recursively(&self, test_count: String, json_name: Arc<String>, json: Arc<Value>) -> JoinHandle<Result<(), String>> {
-        let value_to_search = Arc::clone(&self.value_to_search);
+    fn test_iterate_recursively(test_count: String, json_name: Arc<String>, json: Arc<Value>, search_target: Arc<SearchTarget>) -> JoinHandle<Result<(), String>> {
         task::spawn_blocking(move || {
-            Report::measure(test_count, json_name, MeasurementType::IterateRecursively, move ||
-                assert!(!depth_first_search::run(&json, &value_to_search), "DFS the tree found value that shouldn't be in it: {}", value_to_search)
-            )
+            let is_found = Report::measure(test_count, json_name, MeasurementType::IterateRecursively, || depth_first_search::run(&json, &search_target.value))?;
+            search_target.verify("DFS", is_found, || depth_first_search::find(&json, &search_target.value));
+            Ok(())
+        })
+    }
+
+    /// The same order as the recursive search, so the difference between them is the cost of the recursion.
+    fn test_iterate_with_stack(test_count: String, json_name: Arc<String>, json: Arc<Value>, search_target: Arc<SearchTarget>) -> JoinHandle<Result<(), String>> {
+        task::spawn_blocking(move || {
+            let is_found = Report::measure(test_count, json_name, MeasurementType::IterateWithStack, || iterative_depth_first_search::run(&json, &search_target.value))?;
+            search_target.verify("Stack DFS", is_found, || iterative_depth_first_search::find(&json, &search_target.value));
+            Ok(())
+        })
+    }
+
+    /// Enumerates the path of every match and returns the number of matches, the matches are dropped outside of the measurement.
+    fn test_find_all(test_count: String, json_name: Arc<String>, measurement_type: MeasurementType, json: Arc<Value>, value_to_find: Arc<Value>) -> JoinHandle<Result<u64, String>> {
+        let find_all: FindAllFunction = match measurement_type {
+            MeasurementType::FindAllIteratively => breadth_first_search::find_all,
+            _ => depth_first_search::find_all,
+        };
+        task::spawn_blocking(move || {
+            let search_matches = Report::measure(test_count, json_name, measurement_type, || find_all(&json, &value_to_find))?;
+            Ok(search_matches.len() as u64)
+        })
+    }
+
+    fn test_count_matches(test_count: String, json_name: Arc<String>, measurement_type: MeasurementType, json: Arc<Value>, value_to_find: Arc<Value>) -> JoinHandle<Result<u64, String>> {
+        let count: CountFunction = match measurement_type {
+            MeasurementType::CountIteratively => breadth_first_search::count,
+            _ => depth_first_search::count,
+        };
+        task::spawn_blocking(move || Report::measure(test_count, json_name, measurement_type, || count(&json, &value_to_find)))
+    }
+
+    /// Measures every expression separately, the selected nodes are dropped outside of the measurement.
+    fn test_evaluate_json_paths(test_count: String, json_name: Arc<String>, json: Arc<Value>, json_paths: Arc<Vec<JsonPath>>) -> JoinHandle<Result<(), String>> {
+        task::spawn_blocking(move || {
+            for (json_path_index, json_path) in json_paths.iter().enumerate() {
+                Report::measure(test_count.clone(), Arc::clone(&json_name), MeasurementType::EvaluateJsonPath(json_path_index), ||
+                    json_path.select(&json)
+                )?;
+            }
+            Ok(())
         })
     }
 
@@ -110,4 +259,66 @@ impl RunTestLoop {
             )
         })
     }
+
+    /* #region JSON Lines */
+    async fn test_json_lines(test_count: String, json_name: Arc<String>, number_of_records: u64, raw_json: Arc<String>, search_target: Arc<SearchTarget>)
+    -> Result<(), Box<dyn Error + Send + Sync>> {
+        let records = RunTestLoop::test_deserialize_json_lines(test_count.clone(), Arc::clone(&json_name), number_of_records, raw_json).await??;
+        let records = Arc::new(records);
+        RunTestLoop::test_iterate_json_lines(test_count.clone(), Arc::clone(&json_name), MeasurementType::IterateJsonLinesIteratively, Arc::clone(&records), Arc::clone(&search_target)).await??;
+        RunTestLoop::test_iterate_json_lines(test_count.clone(), Arc::clone(&json_name), MeasurementType::IterateJsonLinesRecursively, Arc::clone(&records), Arc::clone(&search_target)).await??;
+        RunTestLoop::test_iterate_json_lines(test_count.clone(), Arc::clone(&json_name), MeasurementType::IterateJsonLinesWithStack, Arc::clone(&records), search_target).await??;
+        RunTestLoop::test_serialize_json_lines(test_count, json_name, records).await???;
+        Ok(())
+    }
+
+    fn test_deserialize_json_lines(test_count: String, json_name: Arc<String>, number_of_records: u64, raw_json: Arc<String>) -> JoinHandle<Result<Vec<Value>, String>> {
+        task::spawn_blocking(move || {
+            let records = Report::measure(test_count.clone(), Arc::clone(&json_name), MeasurementType::DeserializeJsonLines, move ||
+                json_lines::from_str(&raw_json).expect("Couldn't parse the input JSON Lines")
+            )?;
+            assert_eq!(records.len() as u64, number_of_records, "The input JSON Lines has a different number of records");
+            let node_counts = records.iter().map(NodeCounts::count).sum();
+            REPORT_INSTANCE.blocking_write().set_tested_node_counts(test_count, json_name, node_counts);
+            Ok(records)
+        })
+    }
+
+    /// Searches every record, a planted needle has to be found in all of them.
+    fn test_iterate_json_lines(test_count: String, json_name: Arc<String>, measurement_type: MeasurementType, records: Arc<Vec<Value>>, search_target: Arc<SearchTarget>) -> JoinHandle<Result<(), String>> {
+        let (search, find, search_name): (SearchFunction, FindFunction, &str) = match measurement_type {
+            MeasurementType::IterateJsonLinesIteratively => (breadth_first_search::run, breadth_first_search::find, "BFS"),
+            MeasurementType::IterateJsonLinesWithStack => (iterative_depth_first_search::run, iterative_depth_first_search::find, "Stack DFS"),
+            _ => (depth_first_search::run, depth_first_search::find, "DFS"),
+        };
+        task::spawn_blocking(move || {
+            let number_of_found_records = Report::measure(test_count, json_name, measurement_type, ||
+                records.iter().filter(|record| search(record, &search_target.value)).count()
+            )?;
+            let number_of_expected_records = if search_target.is_planted { records.len() } else { 0 };
+            if number_of_found_records != number_of_expected_records {
+                // The first record that the search got wrong
+                let (record_index, search_match) = records.iter()
+                    .map(|record| find(record, &search_target.value))
+                    .enumerate()
+                    .find(|(_record_index, search_match)| search_match.is_some() != search_target.is_planted)
+                    .expect("A record has a different search result");
+                let location = match search_match {
+                    Some(search_match) => format!(", the first one in record {} as {}", record_index, search_match),
+                    None => format!(", not in record {}", record_index),
+                };
+                panic!("{} found {} in {} of {} records{}", search_name, search_target, number_of_found_records, records.len(), location);
+            }
+            Ok(())
+        })
+    }
+
+    fn test_serialize_json_lines(test_count: String, json_name: Arc<String>, records: Arc<Vec<Value>>) -> JoinHandle<Result<Result<String, serde_json::Error>, String>> {
+        task::spawn_blocking(move || {
+            Report::measure(test_count, json_name, MeasurementType::SerializeJsonLines, move ||
+                json_lines::to_string(&records)
+            )
+        })
+    }
+    /* #endregion */
 }
@@ -0,0 +1,88 @@
+/* #region Imports */
+// Standard
+use std::io::{self, Read};
+/* #endregion */
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+const BUFFER_SIZE: usize = 1 << 16;
+
+/// A 64-bit FNV-1a hash, enough to notice that a generated file changed (not a cryptographic hash).
+pub struct Checksum {
+    hash: u64,
+    size_in_bytes: u64,
+}
+
+impl Checksum {
+    pub fn new() -> Checksum {
+        Checksum {
+            hash: FNV_OFFSET_BASIS,
+            size_in_bytes: 0,
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash ^= u64::from(byte);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+        self.size_in_bytes += bytes.len() as u64;
+    }
+
+    pub fn get_size_in_bytes(&self) -> u64 {
+        self.size_in_bytes
+    }
+
+    /// Formatted as `fnv1a64:` and 16 hexadecimal digits.
+    pub fn get_checksum(&self) -> String {
+        format!("fnv1a64:{:016x}", self.hash)
+    }
+
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Checksum> {
+        let mut checksum = Checksum::new();
+        let mut buffer = vec![0; BUFFER_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(checksum),
+                Ok(bytes_read) => checksum.update(&buffer[..bytes_read]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Checksum {
+        let mut checksum = Checksum::new();
+        checksum.update(bytes);
+        checksum
+    }
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn known_hashes() {
+        assert_eq!(Checksum::from_bytes(b"").get_checksum(), "fnv1a64:cbf29ce484222325");
+        assert_eq!(Checksum::from_bytes(b"a").get_checksum(), "fnv1a64:af63dc4c8601ec8c");
+        assert_eq!(Checksum::from_bytes(b"foobar").get_checksum(), "fnv1a64:85944171f73967e8");
+    }
+
+    #[test]
+    fn reader_matches_bytes() {
+        let bytes: Vec<u8> = (0..200_000).map(|index| (index % 251) as u8).collect();
+        let from_reader = Checksum::from_reader(&bytes[..]).unwrap();
+        assert_eq!(from_reader.get_checksum(), Checksum::from_bytes(&bytes).get_checksum());
+        assert_eq!(from_reader.get_size_in_bytes(), 200_000);
+    }
+}
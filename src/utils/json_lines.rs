@@ -0,0 +1,43 @@
+/* #region Imports */
+// 3rd Party
+use serde_json::{Deserializer, Value};
+/* #endregion */
+
+/// Parses JSON Lines record by record, the records may be separated by any whitespace.
+/// The recursion limit is disabled for deep records, so the calling thread needs a stack big enough for them.
+pub fn from_str(raw_json_lines: &str) -> serde_json::Result<Vec<Value>> {
+    let mut deserializer = Deserializer::from_str(raw_json_lines);
+    deserializer.disable_recursion_limit();
+    deserializer.into_iter::<Value>().collect()
+}
+
+/// Writes every record compactly on its own line, each line ends with a new line.
+pub fn to_string(records: &[Value]) -> serde_json::Result<String> {
+    let mut buffer = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut buffer, record)?;
+        buffer.push(b'\n');
+    }
+    Ok(String::from_utf8(buffer).expect("serde_json writes only UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn round_trip() {
+        let records = vec![json!({"a": [1, 2]}), json!("b"), json!(null)];
+        let raw_json_lines = to_string(&records).unwrap();
+        assert_eq!(raw_json_lines, "{\"a\":[1,2]}\n\"b\"\nnull\n");
+        assert_eq!(from_str(&raw_json_lines).unwrap(), records);
+        assert!(from_str("").unwrap().is_empty());
+        assert!(from_str("{\"a\":1}\n{\"a\":").is_err());
+    }
+}
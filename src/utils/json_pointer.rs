@@ -0,0 +1,62 @@
+/* #region Imports */
+// Standard
+use std::fmt::{self, Write};
+/* #endregion */
+
+/// A single step from a container to its child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathToken<'a> {
+    Index(usize),
+    Key(&'a str),
+}
+
+/// Formats the path as an RFC 6901 JSON Pointer, such as `/a~1b/0` (the root is the empty string).
+pub fn to_json_pointer(path: &[PathToken]) -> String {
+    let mut json_pointer = String::new();
+    for token in path {
+        write_token(&mut json_pointer, token).expect("Writing into a String doesn't fail");
+    }
+    json_pointer
+}
+
+fn write_token<W: Write>(writer: &mut W, token: &PathToken) -> fmt::Result {
+    match *token {
+        PathToken::Index(index) => write!(writer, "/{}", index),
+        PathToken::Key(key) => {
+            writer.write_char('/')?;
+            for character in key.chars() {
+                match character {
+                    '~' => writer.write_str("~0")?,
+                    '/' => writer.write_str("~1")?,
+                    other => writer.write_char(other)?,
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn escapes_keys() {
+        assert_eq!(to_json_pointer(&[]), "");
+        assert_eq!(to_json_pointer(&[PathToken::Key("a/b"), PathToken::Index(0), PathToken::Key("m~n")]), "/a~1b/0/m~0n");
+        assert_eq!(to_json_pointer(&[PathToken::Key("")]), "/");
+    }
+
+    #[test]
+    fn serde_json_resolves_pointer() {
+        let json = json!({ "a/b": [{ "m~n": 1 }] });
+        let json_pointer = to_json_pointer(&[PathToken::Key("a/b"), PathToken::Index(0), PathToken::Key("m~n")]);
+        assert_eq!(json.pointer(&json_pointer), Some(&json!(1)));
+    }
+}
@@ -1,14 +1,20 @@
 /* #region Imports */
+// Standard
+use std::ops::RangeInclusive;
+
 // 3rd Party
 use serde_json::{ Value, Map, json };
 use once_cell::sync::Lazy;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
 
 // Project
 use super::randomizer;
 /* #endregion */
 
 /* #region Enums */
-enum ValueNonLeafType {
+pub enum ValueNonLeafType {
     Array,
     Object,
 }
@@ -30,39 +36,462 @@ enum ValueTypes {
 }
 /* #endregion */
 
+/* #region String Profiles */
 static ALPHABET: Lazy<Vec<char>>  = Lazy::new(|| "AaBbCcDdEeFfGgHhIiJjKkLlMmNnOoPpQqRrSsTtUuVvWwXxYyZz".chars().collect());
 
-fn get_random_none_leaf_json_type() -> ValueNonLeafType {
-    if rand::random() {
-        ValueNonLeafType::Array
-    } else {
+/// Quotes, backslashes and control characters, which have to be escaped in JSON strings.
+static ESCAPED_CHARACTERS: Lazy<Vec<char>> = Lazy::new(|| ['"', '\\', '/']
+    .into_iter()
+    .chain((0..0x20).filter_map(char::from_u32))
+    .collect());
+
+/// Multi-byte characters from the BMP and from the supplementary planes (which are surrogate pairs when escaped).
+static UNICODE_CHARACTERS: Lazy<Vec<char>> = Lazy::new(|| [
+        0xA0..=0xFF, // Latin-1 Supplement
+        0x391..=0x3C9, // Greek
+        0x410..=0x44F, // Cyrillic
+        0x4E00..=0x4EFF, // CJK Unified Ideographs
+        0x1D400..=0x1D4FF, // Mathematical Alphanumeric Symbols
+        0x1F600..=0x1F64F, // Emoticons
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(char::from_u32)
+    .collect());
+
+static ESCAPES_PROFILE_CHARACTERS: Lazy<Vec<char>> = Lazy::new(|| ALPHABET.iter().chain(ESCAPED_CHARACTERS.iter()).copied().collect());
+static UNICODE_PROFILE_CHARACTERS: Lazy<Vec<char>> = Lazy::new(|| ALPHABET.iter().chain(UNICODE_CHARACTERS.iter()).copied().collect());
+static MIXED_PROFILE_CHARACTERS: Lazy<Vec<char>> = Lazy::new(|| ALPHABET
+    .iter()
+    .chain(ESCAPED_CHARACTERS.iter())
+    .chain(UNICODE_CHARACTERS.iter())
+    .copied()
+    .collect());
+
+/// The characters of generated strings, selectable as `alphabet`, `escapes`, `unicode`, `mixed` or `long`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum StringProfile {
+    /// ASCII letters only.
+    #[default]
+    Alphabet,
+    /// ASCII letters with quotes, backslashes and control characters.
+    Escapes,
+    /// ASCII letters with multi-byte and non-BMP characters.
+    Unicode,
+    /// All of the above.
+    Mixed,
+    /// ASCII letters only, with the configured string lengths in kilobytes instead of characters.
+    Long,
+}
+
+impl StringProfile {
+    pub fn get_characters(&self) -> &'static [char] {
+        match self {
+            StringProfile::Alphabet | StringProfile::Long => &ALPHABET,
+            StringProfile::Escapes => &ESCAPES_PROFILE_CHARACTERS,
+            StringProfile::Unicode => &UNICODE_PROFILE_CHARACTERS,
+            StringProfile::Mixed => &MIXED_PROFILE_CHARACTERS,
+        }
+    }
+}
+/* #endregion */
+
+/* #region Number Profiles */
+const SMALL_INTEGERS_RANGE: RangeInclusive<i64> = -1_000..=1_000;
+/// The absolute value of the exponents, big enough to always be written in exponent notation.
+const EXPONENTS_RANGE: RangeInclusive<i32> = 20..=300;
+/// The shortest representation of an f64 has at most 17 significant digits.
+const HIGH_PRECISION_MINIMAL_SIGNIFICANT_DIGITS: usize = 16;
+
+/// The kind of generated numbers, selectable as `float`, `integers`, `smallIntegers`, `extremes`, `exponents`, `highPrecision` or `mixed`.
+/// Only `float` and `integers` are within the configured number range, the other profiles override it with their own ranges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum NumberProfile {
+    /// Floats within the configured number range.
+    #[default]
+    Float,
+    /// Integers within the configured number range.
+    Integers,
+    /// Integers between -1000 and 1000.
+    SmallIntegers,
+    /// The i64 and u64 bounds and random integers of the whole i64 and u64 ranges.
+    Extremes,
+    /// Floats with exponents between 20 and 300 or -300 and -20, written in exponent notation.
+    Exponents,
+    /// Floats between -1 and 1 written with at least 16 significant digits.
+    HighPrecision,
+    /// Any of the above.
+    Mixed,
+}
+
+const VARIANTS_NUMBER_PROFILES: [NumberProfile; 6] = [
+    NumberProfile::Float,
+    NumberProfile::Integers,
+    NumberProfile::SmallIntegers,
+    NumberProfile::Extremes,
+    NumberProfile::Exponents,
+    NumberProfile::HighPrecision,
+];
+
+fn get_random_number_json<R: Rng + ?Sized>(random_generator: &mut R, number_profile: NumberProfile, leaf_options: &LeafOptions) -> Value {
+    match number_profile {
+        NumberProfile::Float => json!(randomizer::get_random_number_in_range(random_generator, leaf_options.minimal_number..=leaf_options.maximal_number)),
+        NumberProfile::Integers => json!(randomizer::get_random_number_in_range(random_generator, leaf_options.get_integers_range())),
+        NumberProfile::SmallIntegers => json!(randomizer::get_random_number_in_range(random_generator, SMALL_INTEGERS_RANGE)),
+        NumberProfile::Extremes => match randomizer::get_random_number_in_range(random_generator, 0..5) {
+            0 => json!(i64::MIN),
+            1 => json!(i64::MAX),
+            2 => json!(u64::MAX),
+            3 => json!(random_generator.gen::<i64>()),
+            _ => json!(random_generator.gen::<u64>()),
+        },
+        NumberProfile::Exponents => {
+            let mantissa = randomizer::get_random_number_in_range(random_generator, 1.0..10.0);
+            let exponent = randomizer::get_random_number_in_range(random_generator, EXPONENTS_RANGE);
+            let exponent = if random_generator.gen() { exponent } else { -exponent };
+            let sign = if random_generator.gen() { 1.0 } else { -1.0 };
+            json!(sign * mantissa * 10f64.powi(exponent))
+        },
+        NumberProfile::HighPrecision => loop {
+            let number = randomizer::get_random_number_in_range(random_generator, -1.0..1.0);
+            if get_number_of_significant_digits(number) >= HIGH_PRECISION_MINIMAL_SIGNIFICANT_DIGITS {
+                break json!(number);
+            }
+        },
+        NumberProfile::Mixed => {
+            let number_profile = *randomizer::get_random_value_from_array(random_generator, &VARIANTS_NUMBER_PROFILES);
+            get_random_number_json(random_generator, number_profile, leaf_options)
+        },
+    }
+}
+
+/// The number of significant digits of the shortest representation that parses back to the same f64.
+fn get_number_of_significant_digits(number: f64) -> usize {
+    let raw_number = format!("{:e}", number);
+    let mantissa = raw_number.split('e').next().unwrap_or_default();
+    mantissa.trim_end_matches('0').chars().filter(char::is_ascii_digit).count()
+}
+/* #endregion */
+
+/* #region Leaf Options */
+/// The relative chance of each leaf type, a zero weight disables the type.
+/// The weights that are missing from the config are zero, so `{"string": 7}` generates only strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafTypeWeights {
+    #[serde(default)]
+    pub null: u32,
+    #[serde(default)]
+    pub bool: u32,
+    #[serde(default)]
+    pub number: u32,
+    #[serde(default)]
+    pub string: u32,
+}
+
+impl LeafTypeWeights {
+    fn get_weight(&self, leaf_type: ValueLeafType) -> u32 {
+        match leaf_type {
+            ValueLeafType::Null => self.null,
+            ValueLeafType::Bool => self.bool,
+            ValueLeafType::Number => self.number,
+            ValueLeafType::String => self.string,
+        }
+    }
+}
+
+impl Default for LeafTypeWeights {
+    fn default() -> Self {
+        LeafTypeWeights { null: 1, bool: 1, number: 1, string: 1 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeafOptions {
+    #[serde(rename = "typeWeights")]
+    pub type_weights: LeafTypeWeights,
+    #[serde(rename = "minimalStringLength")]
+    pub minimal_string_length: usize,
+    #[serde(rename = "maximalStringLength")]
+    pub maximal_string_length: usize,
+    #[serde(rename = "minimalNumber")]
+    pub minimal_number: f64,
+    #[serde(rename = "maximalNumber")]
+    pub maximal_number: f64,
+    #[serde(rename = "stringProfile")]
+    pub string_profile: StringProfile,
+    #[serde(rename = "numberProfile")]
+    pub number_profile: NumberProfile,
+}
+
+impl LeafOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = &self.type_weights;
+        if [weights.null, weights.bool, weights.number, weights.string].iter().all(|&weight| weight == 0) {
+            return Err(String::from("At least one leaf type weight has to be none zero"));
+        }
+        if self.minimal_string_length > self.maximal_string_length {
+            return Err(format!("Invalid string length range: {}..={}", self.minimal_string_length, self.maximal_string_length));
+        }
+        if self.string_profile == StringProfile::Long && self.maximal_string_length.checked_mul(LONG_STRING_LENGTH_UNIT).is_none() {
+            return Err(format!("The maximal string length is too big for long strings: {}", self.maximal_string_length));
+        }
+        if !self.minimal_number.is_finite() || !self.maximal_number.is_finite() || self.minimal_number > self.maximal_number {
+            return Err(format!("Invalid number range: {}..={}", self.minimal_number, self.maximal_number));
+        }
+        if matches!(self.number_profile, NumberProfile::Integers | NumberProfile::Mixed) && self.get_integers_range().is_empty() {
+            return Err(format!("There is no integer in the number range: {}..={}", self.minimal_number, self.maximal_number));
+        }
+        Ok(())
+    }
+
+    /// The number of characters of a string leaf.
+    fn get_string_length_range(&self) -> RangeInclusive<usize> {
+        match self.string_profile {
+            StringProfile::Long => (self.minimal_string_length * LONG_STRING_LENGTH_UNIT)..=(self.maximal_string_length * LONG_STRING_LENGTH_UNIT),
+            _ => self.minimal_string_length..=self.maximal_string_length,
+        }
+    }
+
+    /// The integers of the number range, within the i64 range.
+    fn get_integers_range(&self) -> RangeInclusive<i64> {
+        // The casts saturate at the i64 bounds
+        (self.minimal_number.ceil() as i64)..=(self.maximal_number.floor() as i64)
+    }
+}
+
+impl Default for LeafOptions {
+    fn default() -> Self {
+        LeafOptions {
+            type_weights: LeafTypeWeights::default(),
+            minimal_string_length: 0,
+            maximal_string_length: 32,
+            minimal_number: -1_000_000_000.0,
+            maximal_number: 1_000_000_000.0,
+            string_profile: StringProfile::Alphabet,
+            number_profile: NumberProfile::Float,
+        }
+    }
+}
+/* #endregion */
+
+const LONG_STRING_LENGTH_UNIT: usize = 1 << 10;
+
+/// The object ratio is the chance (between 0 and 1) of generating an object instead of an array.
+pub fn get_random_none_leaf_json_type<R: Rng + ?Sized>(random_generator: &mut R, object_ratio: f64) -> ValueNonLeafType {
+    if random_generator.gen_bool(object_ratio) {
         ValueNonLeafType::Object
+    } else {
+        ValueNonLeafType::Array
     }
 }
 
-pub fn get_random_none_leaf_json() -> Value {
-    match get_random_none_leaf_json_type() {
+pub fn get_random_none_leaf_json<R: Rng + ?Sized>(random_generator: &mut R, object_ratio: f64) -> Value {
+    match get_random_none_leaf_json_type(random_generator, object_ratio) {
         ValueNonLeafType::Array => Value::Array(vec!()),
         ValueNonLeafType::Object => Value::Object(Map::new())
     }
 }
 
-fn get_random_leaf_json_type() -> ValueLeafType {
-    *randomizer::get_random_value_from_array(&VARIANTS_VALUE_LEAF_TYPES)
+fn get_random_leaf_json_type<R: Rng + ?Sized>(random_generator: &mut R, type_weights: &LeafTypeWeights) -> ValueLeafType {
+    let total_weight: u64 = VARIANTS_VALUE_LEAF_TYPES.iter().map(|&leaf_type| u64::from(type_weights.get_weight(leaf_type))).sum();
+    let mut chosen_weight = randomizer::get_random_number_in_range(random_generator, 0..total_weight);
+    for leaf_type in VARIANTS_VALUE_LEAF_TYPES {
+        let weight = u64::from(type_weights.get_weight(leaf_type));
+        if chosen_weight < weight {
+            return leaf_type;
+        }
+        chosen_weight -= weight;
+    }
+    unreachable!("The chosen weight is smaller than the total weight")
 }
 
-pub fn get_random_leaf_json() -> Value {
-    match get_random_leaf_json_type() {
+/// The leaf options have to be valid, see [LeafOptions::validate].
+pub fn get_random_leaf_json<R: Rng + ?Sized>(random_generator: &mut R, leaf_options: &LeafOptions) -> Value {
+    match get_random_leaf_json_type(random_generator, &leaf_options.type_weights) {
         ValueLeafType::Null => Value::Null,
-        ValueLeafType::Bool => Value::Bool(rand::random()),
-        ValueLeafType::Number => json!(randomizer::get_random_number_in_range(-1_000_000_000.0..=1_000_000_000.0)),
+        ValueLeafType::Bool => Value::Bool(random_generator.gen()),
+        ValueLeafType::Number => get_random_number_json(random_generator, leaf_options.number_profile, leaf_options),
         ValueLeafType::String => {
-            let number_of_letters = randomizer::get_random_number_in_range(0..=32);
+            let number_of_letters = randomizer::get_random_number_in_range(random_generator, leaf_options.get_string_length_range());
+            let characters = leaf_options.string_profile.get_characters();
             let mut string_builder = String::with_capacity(number_of_letters);
             for _count in 0..number_of_letters {
-                string_builder.push(*randomizer::get_random_value_from_array(&ALPHABET));
+                string_builder.push(*randomizer::get_random_value_from_array(random_generator, characters));
             }
             Value::String(string_builder)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn only_weighted_leaf_types() {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        let leaf_options = LeafOptions {
+            type_weights: LeafTypeWeights { null: 0, bool: 0, number: 0, string: 1 },
+            minimal_string_length: 3,
+            maximal_string_length: 5,
+            ..LeafOptions::default()
+        };
+
+        for _count in 0..1_000 {
+            match get_random_leaf_json(&mut random_generator, &leaf_options) {
+                Value::String(string) => assert!((3..=5).contains(&string.len()), "Invalid string length: {}", string),
+                other => panic!("Expected only strings: {}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn leaf_types_follow_weights() {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        let leaf_options = LeafOptions {
+            type_weights: LeafTypeWeights { null: 1, bool: 0, number: 2, string: 7 },
+            minimal_number: -5.0,
+            maximal_number: 5.0,
+            ..LeafOptions::default()
+        };
+
+        let mut number_of_strings = 0;
+        for _count in 0..10_000 {
+            match get_random_leaf_json(&mut random_generator, &leaf_options) {
+                Value::String(_) => number_of_strings += 1,
+                Value::Number(number) => assert!((-5.0..=5.0).contains(&number.as_f64().unwrap()), "Invalid number: {}", number),
+                Value::Null => {},
+                other => panic!("Didn't expect: {}", other),
+            }
+        }
+        assert!((6_500..7_500).contains(&number_of_strings), "Expected about 70% strings: {}", number_of_strings);
+    }
+
+    #[test]
+    fn missing_leaf_type_weights_are_zero() {
+        let leaf_options: LeafOptions = serde_json::from_str(r#"{"typeWeights": {"string": 7}}"#).unwrap();
+        assert_eq!(leaf_options.type_weights, LeafTypeWeights { null: 0, bool: 0, number: 0, string: 7 });
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        assert!((0..1_000).all(|_count| get_random_leaf_json(&mut random_generator, &leaf_options).is_string()));
+
+        let leaf_options: LeafOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(leaf_options.type_weights, LeafTypeWeights::default());
+        assert!(serde_json::from_str::<LeafOptions>(r#"{"typeWeights": {}}"#).unwrap().validate().is_err());
+    }
+
+    #[test]
+    fn string_profiles() {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        for string_profile in [StringProfile::Escapes, StringProfile::Unicode, StringProfile::Mixed] {
+            let leaf_options = LeafOptions {
+                type_weights: LeafTypeWeights { null: 0, bool: 0, number: 0, string: 1 },
+                string_profile,
+                ..LeafOptions::default()
+            };
+            let leaves: Vec<Value> = (0..100).map(|_count| get_random_leaf_json(&mut random_generator, &leaf_options)).collect();
+            for leaf in &leaves {
+                let number_of_characters = leaf.as_str().unwrap().chars().count();
+                assert!(leaf_options.get_string_length_range().contains(&number_of_characters), "Unexpected string length: {}", number_of_characters);
+            }
+            let leaves = Value::Array(leaves);
+            let raw_leaves = serde_json::to_string(&leaves).unwrap();
+            assert!(!raw_leaves.is_ascii() || raw_leaves.contains("\\u00"), "Expected escapes or none ASCII characters: {}", raw_leaves);
+            assert_eq!(serde_json::from_str::<Value>(&raw_leaves).unwrap(), leaves);
+        }
+
+        let leaf_options = LeafOptions {
+            type_weights: LeafTypeWeights { null: 0, bool: 0, number: 0, string: 1 },
+            minimal_string_length: 2,
+            maximal_string_length: 3,
+            string_profile: StringProfile::Long,
+            ..LeafOptions::default()
+        };
+        for _count in 0..10 {
+            match get_random_leaf_json(&mut random_generator, &leaf_options) {
+                Value::String(string) => assert!((2_048..=3_072).contains(&string.len()), "Expected a 2KB to 3KB string: {}", string.len()),
+                other => panic!("Expected a string: {}", other),
+            }
+        }
+        assert!(LeafOptions { maximal_string_length: usize::MAX, ..leaf_options }.validate().is_err());
+        assert_eq!("mixed".parse::<StringProfile>().unwrap(), StringProfile::Mixed);
+    }
+
+    fn get_random_numbers(number_profile: NumberProfile) -> Vec<serde_json::Number> {
+        let mut random_generator = randomizer::get_seeded_random_generator(42);
+        let leaf_options = LeafOptions {
+            type_weights: LeafTypeWeights { null: 0, bool: 0, number: 1, string: 0 },
+            number_profile,
+            ..LeafOptions::default()
+        };
+        (0..1_000)
+            .map(|_count| match get_random_leaf_json(&mut random_generator, &leaf_options) {
+                Value::Number(number) => number,
+                other => panic!("Expected only numbers: {}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn number_profiles() {
+        for number in get_random_numbers(NumberProfile::Integers) {
+            assert!(number.as_i64().is_some_and(|number| (-1_000_000_000..=1_000_000_000).contains(&number)), "Expected an integer in the number range: {}", number);
+        }
+
+        for number in get_random_numbers(NumberProfile::SmallIntegers) {
+            assert!(number.as_i64().is_some_and(|number| SMALL_INTEGERS_RANGE.contains(&number)), "Expected a small integer: {}", number);
+        }
+
+        let extremes = get_random_numbers(NumberProfile::Extremes);
+        assert!(extremes.iter().all(|number| number.is_i64() || number.is_u64()), "Expected only integers");
+        assert!(extremes.iter().any(|number| number.as_u64() == Some(u64::MAX)));
+        assert!(extremes.iter().any(|number| number.as_i64() == Some(i64::MIN)));
+
+        for number in get_random_numbers(NumberProfile::Exponents) {
+            assert!(number.to_string().contains('e'), "Expected exponent notation: {}", number);
+        }
+
+        for number in get_random_numbers(NumberProfile::HighPrecision) {
+            let float = number.as_f64().unwrap();
+            assert!((-1.0..1.0).contains(&float), "Expected a number between -1 and 1: {}", number);
+            let raw_number = number.to_string();
+            let mantissa = raw_number.split(['e', 'E']).next().unwrap();
+            let significant_digits = mantissa.trim_start_matches(['-', '0', '.']).chars().filter(char::is_ascii_digit).count();
+            assert!(significant_digits >= HIGH_PRECISION_MINIMAL_SIGNIFICANT_DIGITS, "Expected a high precision number: {}", raw_number);
+            assert_eq!(raw_number.parse::<f64>().unwrap(), float, "Expected the same number after a round trip: {}", raw_number);
+            // Without the float_roundtrip feature serde_json may parse the last digit 1 ULP off
+            let parsed_float = serde_json::from_str::<f64>(&raw_number).unwrap();
+            assert!(float.to_bits().abs_diff(parsed_float.to_bits()) <= 1, "Expected the same number after a round trip: {}", raw_number);
+        }
+
+        let mixed = get_random_numbers(NumberProfile::Mixed);
+        assert!(mixed.iter().any(|number| number.is_f64()) && mixed.iter().any(|number| number.is_u64()));
+    }
+
+    #[test]
+    fn invalid_leaf_options() {
+        let no_weights = LeafOptions {
+            type_weights: LeafTypeWeights { null: 0, bool: 0, number: 0, string: 0 },
+            ..LeafOptions::default()
+        };
+        assert!(no_weights.validate().is_err());
+
+        let invalid_string_length = LeafOptions { minimal_string_length: 5, maximal_string_length: 4, ..LeafOptions::default() };
+        assert!(invalid_string_length.validate().is_err());
+
+        let invalid_number_range = LeafOptions { minimal_number: 1.0, maximal_number: -1.0, ..LeafOptions::default() };
+        assert!(invalid_number_range.validate().is_err());
+
+        let no_integers = LeafOptions { minimal_number: 1.2, maximal_number: 1.8, number_profile: NumberProfile::Integers, ..LeafOptions::default() };
+        assert!(no_integers.validate().is_err());
+
+        assert!(LeafOptions::default().validate().is_ok());
+    }
+}
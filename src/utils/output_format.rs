@@ -0,0 +1,232 @@
+/* #region Imports */
+// Standard
+use std::{error::Error, fmt, io::{self, Write}, str::FromStr};
+
+// 3rd Party
+use serde::{Deserialize, Serialize};
+use serde_json::{ser::Formatter, Serializer, Value};
+/* #endregion */
+
+const DEFAULT_INDENT_WIDTH: u8 = 2;
+
+/// How the JSON is laid out, the amount of whitespace changes how long parsing it takes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum OutputFormat {
+    /// Without any whitespace.
+    #[default]
+    Compact,
+    /// Every member and element on its own line, indented by the indent width (in spaces or tabs) per level.
+    Pretty {
+        #[serde(rename = "indentWidth")]
+        indent_width: u8,
+        #[serde(default, rename = "useTabs")]
+        use_tabs: bool,
+    },
+    /// Every object member on its own line without indentation, the arrays stay compact.
+    OneKeyPerLine,
+}
+
+impl OutputFormat {
+    pub fn is_compact(&self) -> bool {
+        *self == OutputFormat::Compact
+    }
+}
+
+/// Formatted as `compact`, `pretty[:WIDTH[:tabs]]` (2 spaces by default) or `oneKeyPerLine`.
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Compact => write!(formatter, "compact"),
+            OutputFormat::Pretty { indent_width, use_tabs: false } => write!(formatter, "pretty:{}", indent_width),
+            OutputFormat::Pretty { indent_width, use_tabs: true } => write!(formatter, "pretty:{}:tabs", indent_width),
+            OutputFormat::OneKeyPerLine => write!(formatter, "oneKeyPerLine"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = source.trim().split(':').collect();
+        match parts[..] {
+            ["compact"] => Ok(OutputFormat::Compact),
+            ["pretty"] => Ok(OutputFormat::Pretty { indent_width: DEFAULT_INDENT_WIDTH, use_tabs: false }),
+            ["pretty", indent_width] => Ok(OutputFormat::Pretty { indent_width: indent_width.parse()?, use_tabs: false }),
+            ["pretty", indent_width, "tabs"] => Ok(OutputFormat::Pretty { indent_width: indent_width.parse()?, use_tabs: true }),
+            ["oneKeyPerLine"] => Ok(OutputFormat::OneKeyPerLine),
+            _ => Err(Box::from(format!("Invalid output format: {}, expected compact, pretty[:WIDTH[:tabs]] or oneKeyPerLine", source))),
+        }
+    }
+}
+
+/// Writes the whitespace of an [OutputFormat], the pretty format is the same as [serde_json::ser::PrettyFormatter].
+pub struct JsonFormatter {
+    output_format: OutputFormat,
+    indent: Vec<u8>,
+    current_indent: usize,
+    has_value: bool,
+}
+
+impl JsonFormatter {
+    pub fn new(output_format: OutputFormat) -> JsonFormatter {
+        let indent = match output_format {
+            OutputFormat::Pretty { indent_width, use_tabs } => vec![if use_tabs { b'\t' } else { b' ' }; indent_width.into()],
+            _ => Vec::new(),
+        };
+        JsonFormatter { output_format, indent, current_indent: 0, has_value: false }
+    }
+
+    fn is_pretty(&self) -> bool {
+        matches!(self.output_format, OutputFormat::Pretty { .. })
+    }
+
+    fn write_new_line<W: ?Sized + Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b"\n")?;
+        for _level in 0..self.current_indent {
+            writer.write_all(&self.indent)?;
+        }
+        Ok(())
+    }
+
+    fn begin_container<W: ?Sized + Write>(&mut self, writer: &mut W, bracket: &[u8]) -> io::Result<()> {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(bracket)
+    }
+
+    fn end_container<W: ?Sized + Write>(&mut self, writer: &mut W, bracket: &[u8]) -> io::Result<()> {
+        self.current_indent -= 1;
+        if self.has_value {
+            self.write_new_line(writer)?;
+        }
+        writer.write_all(bracket)
+    }
+
+    fn begin_member<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        if !first {
+            writer.write_all(b",")?;
+        }
+        self.write_new_line(writer)
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.is_pretty() {
+            self.begin_container(writer, b"[")
+        } else {
+            writer.write_all(b"[")
+        }
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.is_pretty() {
+            self.end_container(writer, b"]")
+        } else {
+            writer.write_all(b"]")
+        }
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        if self.is_pretty() {
+            self.begin_member(writer, first)
+        } else if first {
+            Ok(())
+        } else {
+            writer.write_all(b",")
+        }
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        if self.is_pretty() {
+            self.has_value = true;
+        }
+        Ok(())
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match self.output_format {
+            OutputFormat::Compact => writer.write_all(b"{"),
+            _ => self.begin_container(writer, b"{"),
+        }
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        match self.output_format {
+            OutputFormat::Compact => writer.write_all(b"}"),
+            _ => self.end_container(writer, b"}"),
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        match self.output_format {
+            OutputFormat::Compact if first => Ok(()),
+            OutputFormat::Compact => writer.write_all(b","),
+            _ => self.begin_member(writer, first),
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.is_pretty() {
+            writer.write_all(b": ")
+        } else {
+            writer.write_all(b":")
+        }
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
+pub fn to_writer<W: Write>(writer: W, json: &Value, output_format: OutputFormat) -> serde_json::Result<()> {
+    let mut serializer = Serializer::with_formatter(writer, JsonFormatter::new(output_format));
+    json.serialize(&mut serializer)
+}
+
+pub fn to_string(json: &Value, output_format: OutputFormat) -> serde_json::Result<String> {
+    let mut buffer = Vec::new();
+    to_writer(&mut buffer, json, output_format)?;
+    Ok(String::from_utf8(buffer).expect("serde_json writes only UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::json;
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn pretty_matches_serde_json() {
+        let json = json!({"a": [1, [], {}, {"b": null}], "c": {"d": "e"}, "f": []});
+        assert_eq!(to_string(&json, OutputFormat::Compact).unwrap(), serde_json::to_string(&json).unwrap());
+        assert_eq!(to_string(&json, "pretty".parse().unwrap()).unwrap(), serde_json::to_string_pretty(&json).unwrap());
+        assert_eq!(to_string(&json!([1, {"a": 2}]), "pretty:1:tabs".parse().unwrap()).unwrap(), "[\n\t1,\n\t{\n\t\t\"a\": 2\n\t}\n]");
+    }
+
+    #[test]
+    fn one_key_per_line() {
+        let json = json!({"a": [1, {"b": 2, "c": {}}], "d": {"e": 3}});
+        assert_eq!(to_string(&json, OutputFormat::OneKeyPerLine).unwrap(), "{\n\"a\":[1,{\n\"b\":2,\n\"c\":{}\n}],\n\"d\":{\n\"e\":3\n}\n}");
+    }
+
+    #[test]
+    fn parse_output_format() {
+        for output_format in [
+            OutputFormat::Compact,
+            OutputFormat::Pretty { indent_width: 4, use_tabs: false },
+            OutputFormat::Pretty { indent_width: 1, use_tabs: true },
+            OutputFormat::OneKeyPerLine,
+        ] {
+            assert_eq!(output_format.to_string().parse::<OutputFormat>().unwrap(), output_format);
+        }
+        assert!("pretty:spaces".parse::<OutputFormat>().is_err());
+    }
+}
@@ -1,23 +1,35 @@
 /* #region Imports */
 // 3rd Party
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use rand::distributions::uniform::{SampleUniform, SampleRange};
 /* #endregion */
 
-pub fn get_random_number_in_range<T, R>(range: R) -> T
-where T: SampleUniform, R: SampleRange<T> {
-    let mut rng = rand::thread_rng();
-    rng.gen_range(range)
+pub fn get_random_seed() -> u64 {
+    rand::random()
 }
 
-fn get_random_index_from_array<T>(array: &[T]) -> usize {
-    get_random_number_in_range(0..array.len())
+/// Unlike `StdRng`, the algorithm is fixed, so a seed generates the same JSON with every version of rand.
+pub fn get_seeded_random_generator(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
 }
 
-pub fn get_random_value_from_array<T>(array: &[T]) -> &T {
-    &array[get_random_index_from_array(array)]
+pub fn get_random_number_in_range<T, R, G>(random_generator: &mut G, range: R) -> T
+where T: SampleUniform, R: SampleRange<T>, G: Rng + ?Sized {
+    random_generator.gen_range(range)
 }
 
-pub fn get_random_value_from_array_as_mut<T>(array: &mut [T]) -> &mut T {
-    &mut array[get_random_index_from_array(array)]
-}
\ No newline at end of file
+fn get_random_index_from_array<T, G>(random_generator: &mut G, array: &[T]) -> usize
+where G: Rng + ?Sized {
+    get_random_number_in_range(random_generator, 0..array.len())
+}
+
+pub fn get_random_value_from_array<'a, T, G>(random_generator: &mut G, array: &'a [T]) -> &'a T
+where G: Rng + ?Sized {
+    &array[get_random_index_from_array(random_generator, array)]
+}
+
+pub fn get_random_value_from_array_as_mut<'a, T, G>(random_generator: &mut G, array: &'a mut [T]) -> &'a mut T
+where G: Rng + ?Sized {
+    &mut array[get_random_index_from_array(random_generator, array)]
+}
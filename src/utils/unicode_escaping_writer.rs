@@ -0,0 +1,90 @@
+/* #region Imports */
+// Standard
+use std::{io::{self, Write}, str};
+/* #endregion */
+
+/// Writes every none ASCII character of the JSON as a `\uXXXX` escape (a surrogate pair for characters outside the BMP).
+/// JSON only has none ASCII characters inside strings, so the output is still the same JSON.
+pub struct UnicodeEscapingWriter<W: Write> {
+    writer: W,
+    /// The start of a multi-byte character that was split between writes.
+    pending: Vec<u8>,
+}
+
+impl <W: Write> UnicodeEscapingWriter<W> {
+    pub fn new(writer: W) -> UnicodeEscapingWriter<W> {
+        UnicodeEscapingWriter {
+            writer,
+            pending: vec!(),
+        }
+    }
+}
+
+fn write_escaped<W: Write>(writer: &mut W, text: &str) -> io::Result<()> {
+    let mut ascii_start = 0;
+    for (index, character) in text.char_indices() {
+        if character.is_ascii() {
+            continue;
+        }
+        writer.write_all(&text.as_bytes()[ascii_start..index])?;
+        for code_unit in character.encode_utf16(&mut [0; 2]) {
+            write!(writer, "\\u{:04x}", code_unit)?;
+        }
+        ascii_start = index + character.len_utf8();
+    }
+    writer.write_all(&text.as_bytes()[ascii_start..])
+}
+
+impl <W: Write> Write for UnicodeEscapingWriter<W> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buffer);
+        let valid_length = match str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        let text = str::from_utf8(&self.pending[..valid_length]).expect("The length was already validated");
+        write_escaped(&mut self.writer, text)?;
+        self.pending.drain(..valid_length);
+
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    /* #region Imports */
+    // 3rd Party
+    use serde_json::{json, Value};
+
+    // Project
+    use super::*;
+    /* #endregion */
+
+    #[test]
+    fn escapes_none_ascii_characters() {
+        let mut output = vec!();
+        UnicodeEscapingWriter::new(&mut output).write_all("{\"é\":\"a😀\\n\"}".as_bytes()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "{\"\\u00e9\":\"a\\ud83d\\ude00\\n\"}");
+    }
+
+    #[test]
+    fn character_split_between_writes() {
+        let json = json!({ "中": ["😀", "ж", "plain"] });
+        let raw_json = serde_json::to_vec(&json).unwrap();
+
+        let mut output = vec!();
+        {
+            let mut writer = UnicodeEscapingWriter::new(&mut output);
+            for byte in raw_json.iter() {
+                writer.write_all(&[*byte]).unwrap();
+            }
+        }
+        assert!(output.is_ascii());
+        assert_eq!(serde_json::from_slice::<Value>(&output).unwrap(), json);
+    }
+}